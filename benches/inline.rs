@@ -0,0 +1,71 @@
+//! Compares the parsing of FQDN into a heap-allocated [`FQDN`] and into an [`InlineFqdn`].
+//!
+//! The number of allocations per parsing is counted by a global allocator
+//! and printed before running the benchmarks.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use fqdn::*;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const NAMES: [&str; 4] = [
+    "com.",
+    "github.com.",
+    "www.rust-lang.github.io.",
+    "a-rather-long-label.with-many.labels.in-order.to-be.representative.example.org.",
+];
+
+const BYTES: &[u8] = b"\x03www\x09rust-lang\x06github\x02io\x00";
+
+fn allocations<T>(f: impl Fn() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn parsing(c: &mut Criterion) {
+    println!("allocations per parsing of {} names: FQDN = {}, InlineFqdn = {}",
+             NAMES.len(),
+             allocations(|| NAMES.map(|s| FQDN::from_ascii_str(s).unwrap())),
+             allocations(|| NAMES.map(|s| InlineFqdn::from_ascii_str(s).unwrap())));
+
+    let mut group = c.benchmark_group("from_ascii_str");
+    group.bench_function("FQDN", |b| b.iter(|| {
+        NAMES.map(|s| FQDN::from_ascii_str(black_box(s)).unwrap())
+    }));
+    group.bench_function("InlineFqdn", |b| b.iter(|| {
+        NAMES.map(|s| InlineFqdn::from_ascii_str(black_box(s)).unwrap())
+    }));
+    group.finish();
+
+    let mut group = c.benchmark_group("from_bytes");
+    group.bench_function("FQDN", |b| b.iter(|| {
+        Fqdn::from_bytes(black_box(BYTES)).unwrap().to_owned()
+    }));
+    group.bench_function("InlineFqdn", |b| b.iter(|| {
+        InlineFqdn::from_bytes(black_box(BYTES)).unwrap()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, parsing);
+criterion_main!(benches);
@@ -1,15 +1,13 @@
 use fqdn::*;
 
 fn main() {
-    let fqdn = r"foo.bar";
-
-    //let fqdn = dbg!(fqdn!("foo.bar"));
+    let fqdn = fqdn!("foo.bar");
     println!("fqdn = {fqdn:?} => {fqdn}");
 
     // Convert the FQDN to a JSON string.
     let serialized = serde_json::to_string(&fqdn).unwrap();
 
-    // Prints serialized = [3,102,111,111,3,98,97,114]
+    // Prints serialized = "foo.bar"
     println!("serialized = {serialized}");
 
     // Convert the JSON string back to a FQDN.
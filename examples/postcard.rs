@@ -4,15 +4,19 @@ fn main() {
     let fqdn = fqdn!("foo.bar");
     println!("fqdn = {fqdn:?} => {fqdn}");
 
-    // Convert the FQDN to a JSON string.
+    // Convert the FQDN to postcard bytes (its byte sequence prefixed by its length).
     let serialized = postcard::to_stdvec(&fqdn).unwrap();
 
-    // Prints serialized = [3,102,111,111,3,98,97,114]
+    // Prints serialized = [9, 3, 102, 111, 111, 3, 98, 97, 114, 0]
     println!("serialized = {serialized:?}");
 
-    // Convert the JSON string back to a FQDN.
+    // Convert the postcard bytes back to a FQDN.
     let deserialized: FQDN = postcard::from_bytes(&serialized).unwrap();
 
+    // or borrow it directly from the postcard bytes, without any copy
+    let borrowed: &Fqdn = postcard::from_bytes(&serialized).unwrap();
+    assert_eq!(*borrowed, fqdn);
+
     // Prints deserialized
     println!("deserialized = {deserialized:?} => {deserialized}");
 
@@ -0,0 +1,14 @@
+#![no_main]
+
+use fqdn::{Fqdn, FQDN};
+use libfuzzer_sys::fuzz_target;
+
+// any accepted string leads to a valid byte sequence which is displayed as the lowercase string
+fuzz_target!(|s: &str| {
+    if let Ok(fqdn) = FQDN::from_ascii_str(s) {
+        assert_eq!(Fqdn::from_bytes(fqdn.as_bytes()), Ok(fqdn.as_ref()));
+        let displayed = fqdn.to_string();
+        assert!(displayed.trim_end_matches('.').eq_ignore_ascii_case(s.trim_end_matches('.')), "{s}");
+        assert_eq!(FQDN::from_ascii_str(&displayed).as_ref(), Ok(&fqdn));
+    }
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+use fqdn::{Fqdn, FQDN};
+use libfuzzer_sys::fuzz_target;
+
+// any accepted byte sequence is kept as is and is displayed as a parsable string
+fuzz_target!(|data: &[u8]| {
+    if let Ok(fqdn) = Fqdn::from_bytes(data) {
+        assert_eq!(fqdn.as_bytes(), data);
+        assert_eq!(FQDN::from_vec(data.to_vec()).as_deref(), Ok(fqdn));
+        assert_eq!(FQDN::from_ascii_str(&fqdn.to_string()).as_deref(), Ok(fqdn));
+    }
+});
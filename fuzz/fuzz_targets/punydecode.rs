@@ -0,0 +1,14 @@
+#![no_main]
+
+use fqdn::FQDN;
+use libfuzzer_sys::fuzz_target;
+
+// decoding never fails (even with invalid punycode) and is reverted by encoding
+fuzz_target!(|fqdn: FQDN| {
+    let decoded = fqdn.punydecode();
+    if fqdn.labels().all(|label| !label.starts_with("xn--")) {
+        assert_eq!(FQDN::punyencode(&decoded).as_ref(), Ok(&fqdn));
+    } else if let Ok(encoded) = FQDN::punyencode(&decoded) {
+        assert_eq!(FQDN::punyencode(encoded.punydecode()).as_ref(), Ok(&encoded));
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use fqdn::{Fqdn, FQDN};
+use libfuzzer_sys::fuzz_target;
+
+// any encoded string is decoded to a string which is encoded again to the same FQDN
+fuzz_target!(|s: &str| {
+    if let Ok(fqdn) = FQDN::punyencode(s) {
+        assert_eq!(Fqdn::from_bytes(fqdn.as_bytes()), Ok(fqdn.as_ref()));
+        // labels already encoded by the input are not necessarily canonical
+        if !fqdn.labels().any(|label| label.starts_with("xn--") && s.to_lowercase().contains(label)) {
+            assert_eq!(FQDN::punyencode(fqdn.punydecode()).as_ref(), Ok(&fqdn), "{s}");
+        }
+    }
+});
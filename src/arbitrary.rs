@@ -0,0 +1,87 @@
+use alloc::vec::Vec;
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::check::{MAX_LABEL_LEN, MAX_NAME_LEN};
+use crate::FQDN;
+
+// the chars allowed at any position of a label
+#[cfg(not(feature = "domain-name-without-special-chars"))]
+const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-_#";
+#[cfg(feature = "domain-name-without-special-chars")]
+const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+// the chars allowed at the first and last positions of a label
+#[cfg(feature = "domain-label-cannot-start-or-end-with-hyphen")]
+const EDGE_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+#[cfg(not(feature = "domain-label-cannot-start-or-end-with-hyphen"))]
+const EDGE_CHARSET: &[u8] = CHARSET;
+
+impl<'a> Arbitrary<'a> for FQDN {
+    /// Generates a FQDN which is valid whatever the activated features.
+    ///
+    /// Any input produces a FQDN, so that a fuzzer never wastes its time on invalid names.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use arbitrary::{Arbitrary, Unstructured};
+    ///
+    /// let mut data = Unstructured::new(b"\x01\x05github\x01\x02io");
+    /// let fqdn = FQDN::arbitrary(&mut data).unwrap();
+    /// assert![ Fqdn::from_bytes(fqdn.as_bytes()).is_ok() ];
+    /// ```
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self>
+    {
+        let mut bytes = Vec::new();
+        // stops when the data is exhausted (then, `false` is generated)
+        while bytes.len() + 2 < MAX_NAME_LEN && u.arbitrary()? {
+            let len = u.int_in_range(1..=MAX_LABEL_LEN.min(MAX_NAME_LEN - bytes.len() - 2))?;
+            bytes.push(len as u8);
+            for i in 0..len {
+                let charset = if i == 0 || i == len - 1 { EDGE_CHARSET } else { CHARSET };
+                bytes.push(*u.choose(charset)?);
+            }
+        }
+        #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+        if crate::check::has_numeric_tld(&bytes) {
+            *bytes.last_mut().unwrap() = b'a';
+        }
+        bytes.push(0);
+
+        debug_assert!(crate::check::check_byte_sequence(&bytes).is_ok());
+        // it is safe since the labels are built with the allowed chars and lengths
+        Ok(unsafe { FQDN::from_vec_with_nul_unchecked(bytes) })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::arbitrary::{Arbitrary, Unstructured};
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn always_valid()
+    {
+        // a simple pseudo-random generator is enough to get various inputs
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        let data = (0..1 << 16)
+            .map(|_| { seed ^= seed << 13; seed ^= seed >> 7; seed ^= seed << 17; seed as u8 })
+            .collect::<Vec<_>>();
+
+        let mut u = Unstructured::new(&data);
+        let mut depths = [0; 4];
+        while !u.is_empty() {
+            let fqdn = FQDN::arbitrary(&mut u).unwrap();
+            assert_eq!(Fqdn::from_bytes(fqdn.as_bytes()), Ok(fqdn.as_ref()));
+            assert_eq!(fqdn.to_string().parse::<FQDN>().as_ref(), Ok(&fqdn), "{fqdn}");
+            depths[fqdn.depth().min(3)] += 1;
+        }
+        assert!(depths.iter().all(|&n| n > 0), "{depths:?}");
+
+        // exhausted data
+        assert_eq!(FQDN::arbitrary(&mut Unstructured::new(&[])).unwrap(), FQDN::default());
+    }
+}
@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+use crate::{Fqdn, FQDN};
+
+/// The identifier of a FQDN stored in a [`FqdnArena`].
+///
+/// An identifier is only meaningful for the arena which produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NameId(u32);
+
+// a FQDN is stored as its first label and a reference to its parent
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    parent: NameId,
+    label: u32,
+    depth: u32,
+}
+
+/// A storage of FQDN where suffixes are shared.
+///
+/// Each stored FQDN is only made of its first label and of a reference to its parent,
+/// which is also stored in the arena. So, storing a lot of FQDN of the same zone
+/// (e.g. `*.svc.cluster.local.`) costs little more than their first labels.
+/// Moreover, labels are also shared between all the stored FQDN.
+///
+/// Stored FQDN are designated by a [`NameId`] and could be inspected through an [`ArenaName`]
+/// view or converted back to an [`FQDN`] on demand.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let mut arena = FqdnArena::new();
+/// let www = arena.insert(&fqdn!("www.rust-lang.github.io"));
+/// let docs = arena.insert(&fqdn!("docs.rust-lang.github.io"));
+/// let io = arena.get(&fqdn!("io")).unwrap();
+///
+/// assert_eq![ arena.len(), 6 ]; // including all the parents, up to the top domain
+/// assert![ arena.is_subdomain_of(www, io) ];
+/// assert_eq![ arena.name(docs).to_fqdn(), fqdn!("docs.rust-lang.github.io") ];
+/// ```
+#[derive(Debug, Clone)]
+pub struct FqdnArena {
+    nodes: Vec<Node>,
+    children: HashMap<(NameId, u32), NameId>,
+    labels: Vec<Arc<str>>,
+    label_ids: HashMap<Arc<str>, u32>,
+}
+
+impl Default for FqdnArena {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+impl FqdnArena {
+
+    /// Creates an arena which only contains the top domain.
+    pub fn new() -> Self
+    {
+        FqdnArena {
+            // the top domain is its own parent (and its label is never used)
+            nodes: vec![Node { parent: NameId(0), label: 0, depth: 0 }],
+            children: HashMap::new(),
+            labels: vec![Arc::from("")],
+            label_ids: HashMap::new(),
+        }
+    }
+
+    /// Gets the identifier of the top domain.
+    #[inline]
+    pub fn root(&self) -> NameId { NameId(0) }
+
+    /// Returns the number of stored FQDN, including the top domain.
+    #[inline]
+    pub fn len(&self) -> usize { self.nodes.len() }
+
+    /// Checks if only the top domain is stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.nodes.len() == 1 }
+
+    /// Stores a FQDN (and all its parents), if not already done.
+    pub fn insert(&mut self, fqdn: &Fqdn) -> NameId
+    {
+        fqdn.labels().rev().fold(self.root(), |parent, label| self.insert_child(parent, label))
+    }
+
+    /// Gets the identifier of an already stored FQDN.
+    pub fn get(&self, fqdn: &Fqdn) -> Option<NameId>
+    {
+        fqdn.labels().rev().try_fold(self.root(), |parent, label| {
+            let label = *self.label_ids.get(label)?;
+            self.children.get(&(parent, label)).copied()
+        })
+    }
+
+    /// Gets a view on a stored FQDN.
+    ///
+    /// # Panics
+    /// If the identifier does not come from this arena, this function may panic.
+    #[inline]
+    pub fn name(&self, id: NameId) -> ArenaName<'_> { ArenaName { arena: self, id } }
+
+    /// Gets the parent of a stored FQDN (`None` for the top domain).
+    #[inline]
+    pub fn parent(&self, id: NameId) -> Option<NameId>
+    {
+        if id == self.root() { None } else { Some(self.node(id).parent) }
+    }
+
+    /// Gets the first label of a stored FQDN (the empty string for the top domain).
+    #[inline]
+    pub fn label(&self, id: NameId) -> &str { &self.labels[self.node(id).label as usize] }
+
+    /// Gets the depth of a stored FQDN (i.e. its number of labels).
+    #[inline]
+    pub fn depth(&self, id: NameId) -> usize { self.node(id).depth as usize }
+
+    /// Checks if a stored FQDN is a descendant of another one.
+    ///
+    /// This only follows the parent references, so it is done in O(depth).
+    pub fn is_subdomain_of(&self, id: NameId, parent: NameId) -> bool
+    {
+        match self.depth(id).checked_sub(self.depth(parent)) {
+            None => false,
+            Some(diff) => (0..diff).fold(id, |id, _| self.node(id).parent) == parent
+        }
+    }
+
+    fn insert_child(&mut self, parent: NameId, label: &str) -> NameId
+    {
+        let label = match self.label_ids.get(label) {
+            Some(&label) => label,
+            None => {
+                let id = self.labels.len() as u32;
+                let label: Arc<str> = label.into();
+                self.labels.push(label.clone());
+                self.label_ids.insert(label, id);
+                id
+            }
+        };
+        match self.children.get(&(parent, label)) {
+            Some(&child) => child,
+            None => {
+                let child = NameId(self.nodes.len() as u32);
+                let depth = self.node(parent).depth + 1;
+                self.nodes.push(Node { parent, label, depth });
+                self.children.insert((parent, label), child);
+                child
+            }
+        }
+    }
+
+    #[inline]
+    fn node(&self, id: NameId) -> &Node { &self.nodes[id.0 as usize] }
+}
+
+
+/// A view on a FQDN stored in a [`FqdnArena`].
+///
+/// Two views are equal if they designate the same FQDN in the same arena.
+#[derive(Clone, Copy)]
+pub struct ArenaName<'a> {
+    arena: &'a FqdnArena,
+    id: NameId,
+}
+
+impl<'a> ArenaName<'a> {
+
+    /// Gets the identifier of this FQDN in the arena.
+    #[inline]
+    pub fn id(&self) -> NameId { self.id }
+
+    /// Checks if this is the top domain.
+    #[inline]
+    pub fn is_root(&self) -> bool { self.id == self.arena.root() }
+
+    /// Gets the immediate parent domain (`None` for the top domain).
+    #[inline]
+    pub fn parent(&self) -> Option<ArenaName<'a>> { self.arena.parent(self.id).map(|id| self.arena.name(id)) }
+
+    /// Computes the depth of this domain (i.e. counts the labels).
+    #[inline]
+    pub fn depth(&self) -> usize { self.arena.depth(self.id) }
+
+    /// Checks if this domain is a descendant of another one of the same arena.
+    #[inline]
+    pub fn is_subdomain_of(&self, parent: &ArenaName) -> bool
+    {
+        std::ptr::eq(self.arena, parent.arena) && self.arena.is_subdomain_of(self.id, parent.id)
+    }
+
+    /// Iterates over the labels, starting from the leftmost one.
+    #[inline]
+    pub fn labels(&self) -> ArenaLabels<'a> { ArenaLabels { arena: self.arena, id: self.id } }
+
+    /// Builds the corresponding FQDN.
+    pub fn to_fqdn(&self) -> FQDN
+    {
+        let mut bytes = Vec::with_capacity(self.labels().map(|l| l.len() + 1).sum::<usize>() + 1);
+        self.labels().for_each(|label| {
+            bytes.push(label.len() as u8);
+            bytes.extend_from_slice(label.as_bytes());
+        });
+        bytes.push(0);
+        // it is safe since all the labels come from valid FQDN
+        unsafe { FQDN::from_vec_with_nul_unchecked(bytes) }
+    }
+}
+
+impl PartialEq for ArenaName<'_> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { std::ptr::eq(self.arena, other.arena) && self.id == other.id }
+}
+
+impl Eq for ArenaName<'_> { }
+
+impl PartialEq<Fqdn> for ArenaName<'_> {
+    #[inline]
+    fn eq(&self, other: &Fqdn) -> bool { self.depth() == other.depth() && self.labels().eq(other.labels()) }
+}
+
+impl PartialEq<FQDN> for ArenaName<'_> {
+    #[inline]
+    fn eq(&self, other: &FQDN) -> bool { self.eq(other.as_ref()) }
+}
+
+impl fmt::Display for ArenaName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        // the same rules as for Fqdn are applied
+        if self.is_root() {
+            f.write_str(".")
+        } else {
+            let mut iter = self.labels();
+
+            #[cfg(feature="domain-name-should-have-trailing-dot")] {
+                iter.try_for_each(|s| { f.write_str(s)?; f.write_str(".") })
+            }
+            #[cfg(not(feature="domain-name-should-have-trailing-dot"))] {
+                f.write_str(iter.next().unwrap())?;
+                iter.try_for_each(|s| { f.write_str(".")?; f.write_str(s)  })
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ArenaName<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_tuple("ArenaName").field(&self.id).field(&format_args!("{self}")).finish()
+    }
+}
+
+/// An iterator over the labels of a FQDN stored in a [`FqdnArena`].
+///
+/// This struct is created by [`ArenaName::labels`].
+#[derive(Debug, Clone)]
+pub struct ArenaLabels<'a> {
+    arena: &'a FqdnArena,
+    id: NameId,
+}
+
+impl<'a> Iterator for ArenaLabels<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let parent = self.arena.parent(self.id)?;
+        let label = self.arena.label(self.id);
+        self.id = parent;
+        Some(label)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let depth = self.arena.depth(self.id);
+        (depth, Some(depth))
+    }
+}
+
+impl ExactSizeIterator for ArenaLabels<'_> { }
+
+impl FusedIterator for ArenaLabels<'_> { }
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn sharing()
+    {
+        let mut arena = FqdnArena::new();
+        assert!(arena.is_empty());
+
+        let names = (0..1000)
+            .map(|i| arena.insert(&format!("pod-{i}.default.svc.cluster.local.").parse::<FQDN>().unwrap()))
+            .collect::<Vec<_>>();
+
+        // 1000 pods + default + svc + cluster + local + root
+        assert_eq!(arena.len(), 1005);
+        assert_eq!(arena.insert(&fqdn!("POD-7.default.svc.cluster.local")), names[7]);
+        assert_eq!(arena.get(&fqdn!("pod-7.default.svc.cluster.local")), Some(names[7]));
+        assert_eq!(arena.get(&fqdn!("pod-7.svc.cluster.local")), None);
+        assert_eq!(arena.get(&fqdn!(".")), Some(arena.root()));
+
+        // labels are shared too
+        let other = arena.insert(&fqdn!("local.cluster.default"));
+        assert_eq!(arena.len(), 1008);
+        assert_eq!(arena.name(other), fqdn!("local.cluster.default"));
+    }
+
+    #[test]
+    fn hierarchy()
+    {
+        let mut arena = FqdnArena::new();
+        let www = arena.insert(&fqdn!("www.rust-lang.github.io"));
+        let github = arena.get(&fqdn!("github.io")).unwrap();
+        let crates = arena.insert(&fqdn!("crates.io"));
+
+        assert!(arena.is_subdomain_of(www, github));
+        assert!(arena.is_subdomain_of(www, www));
+        assert!(arena.is_subdomain_of(www, arena.root()));
+        assert!(!arena.is_subdomain_of(github, www));
+        assert!(!arena.is_subdomain_of(www, crates));
+
+        let name = arena.name(www);
+        assert_eq!(name.depth(), 4);
+        assert_eq!(arena.label(www), "www");
+        assert_eq!(name.parent().and_then(|p| p.parent()), Some(arena.name(github)));
+        assert!(name.labels().eq(["www", "rust-lang", "github", "io"]));
+        assert_eq!(name.to_fqdn(), fqdn!("www.rust-lang.github.io"));
+        assert_eq!(name.to_string(), fqdn!("www.rust-lang.github.io").to_string());
+        assert_eq!(arena.name(arena.root()).to_fqdn(), FQDN::default());
+        assert_eq!(arena.name(arena.root()).to_string(), ".");
+        assert!(arena.name(arena.root()).parent().is_none());
+    }
+}
@@ -0,0 +1,256 @@
+//! Command-line tool to check, convert and sort FQDN.
+//!
+//! Names are read from the arguments or, if there are none, from the standard input (one per line).
+//! Invalid names are reported on the standard error and make the exit status fail,
+//! but they do not stop the processing of the other ones.
+use std::io::{self, BufRead, BufWriter, Write};
+use std::net::IpAddr;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use fqdn::{try_fqdn, Error, Fqdn, FQDN};
+
+/// Check, convert and sort fully qualified domain names
+///
+/// Names are read from the arguments or, if there are none, from the standard input (one per line).
+#[derive(Debug, Parser)]
+#[command(name = "fqdn", version)]
+struct Cli {
+    /// The rules applied when parsing names
+    #[arg(short, long, value_enum, default_value_t = Policy::Default, global = true)]
+    policy: Policy,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Policy {
+    /// The trailing dot is optional, whatever the features of this build
+    Relaxed,
+    /// The rules selected by the features of this build
+    Default,
+    /// All the rules of the RFC, including the trailing dot
+    Strict,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Validate names and print a diagnostic for each invalid one
+    Check {
+        /// Also print the valid names
+        #[arg(short, long)]
+        verbose: bool,
+        names: Vec<String>,
+    },
+    /// Print names in lowercase, with their trailing dot
+    Normalize { names: Vec<String> },
+    /// Convert internationalized names to punycode
+    Encode { names: Vec<String> },
+    /// Convert punycode names to unicode
+    Decode { names: Vec<String> },
+    /// Dump the wire format of names in hexadecimal
+    Wire { names: Vec<String> },
+    /// Print the number of labels of names
+    Depth { names: Vec<String> },
+    /// Print the top level domain of names (an empty line for the top domain)
+    Tld { names: Vec<String> },
+    /// Print the parent domain of names (an empty line for top level domains and the top domain)
+    Parent { names: Vec<String> },
+    /// Print a name and all its ancestors, separated by spaces
+    Hierarchy { names: Vec<String> },
+    /// Print the names used for reverse lookups (PTR) of IP addresses
+    Ptr { addresses: Vec<String> },
+    /// Sort names in the canonical DNS order (RFC 4034)
+    Sort {
+        /// Remove duplicated names
+        #[arg(short, long)]
+        unique: bool,
+        names: Vec<String>,
+    },
+}
+
+impl Policy {
+    fn parse(self, name: &str) -> Result<FQDN, Error>
+    {
+        match self {
+            Policy::Relaxed => try_fqdn!(name),
+            Policy::Default => name.parse(),
+            Policy::Strict if !name.ends_with('.') => Err(Error::TrailingDotMissing),
+            Policy::Strict => {
+                let fqdn = name.parse::<FQDN>()?;
+                fqdn.check_strict_rfc().map(|_| fqdn)
+            }
+        }
+    }
+}
+
+// Formats a name with its trailing dot, whatever the features of this build
+fn dotted(fqdn: &Fqdn) -> String
+{
+    let mut s = fqdn.to_string();
+    if !s.ends_with('.') {
+        s.push('.');
+    }
+    s
+}
+
+// Iterates over the names given as arguments or, if any, over the lines of the input
+fn inputs<'a>(args: &'a [String], input: impl BufRead + 'a) -> Box<dyn Iterator<Item = io::Result<String>> + 'a>
+{
+    if args.is_empty() {
+        Box::new(input.lines().filter(|line| !matches!(line, Ok(line) if line.trim().is_empty())))
+    } else {
+        Box::new(args.iter().cloned().map(Ok))
+    }
+}
+
+// Runs a command and returns whether all the names were valid
+fn run(cli: &Cli, input: impl BufRead, out: &mut impl Write, err: &mut impl Write) -> io::Result<bool>
+{
+    let mut valid = true;
+    let mut report = |name: &str, e: &dyn std::fmt::Display, out: &mut dyn Write| {
+        valid = false;
+        writeln!(out, "{name}: {e}")
+    };
+
+    let names = match &cli.command {
+        Command::Ptr { addresses } => {
+            for address in inputs(addresses, input) {
+                let address = address?;
+                match address.trim().parse::<IpAddr>() {
+                    Ok(ip) => writeln!(out, "{}", dotted(&FQDN::reverse_pointer(ip)))?,
+                    Err(e) => report(&address, &e, err)?,
+                }
+            }
+            return Ok(valid);
+        }
+        Command::Check { names, .. } | Command::Normalize { names } | Command::Encode { names }
+            | Command::Decode { names } | Command::Wire { names } | Command::Depth { names }
+            | Command::Tld { names } | Command::Parent { names } | Command::Hierarchy { names }
+            | Command::Sort { names, .. } => names
+    };
+
+    let mut sorted = Vec::new();
+    for name in inputs(names, input) {
+        let name = name?;
+        let fqdn = match cli.policy.parse(name.trim()) {
+            Ok(fqdn) => fqdn,
+            Err(e) if matches!(cli.command, Command::Check { .. }) => { report(&name, &e, out)?; continue }
+            Err(e) => { report(&name, &e, err)?; continue }
+        };
+        match &cli.command {
+            Command::Check { verbose, .. } => if *verbose { writeln!(out, "{name}: ok")? },
+            Command::Normalize { .. } | Command::Encode { .. } => writeln!(out, "{}", dotted(&fqdn))?,
+            Command::Decode { .. } => {
+                let decoded = fqdn.punydecode();
+                writeln!(out, "{decoded}{}", if decoded.ends_with('.') { "" } else { "." })?
+            }
+            Command::Wire { .. } => {
+                fqdn.as_bytes().iter().try_for_each(|b| write!(out, "{b:02x}"))?;
+                writeln!(out)?
+            }
+            Command::Depth { .. } => writeln!(out, "{}", fqdn.depth())?,
+            Command::Tld { .. } => writeln!(out, "{}", fqdn.tld().map(dotted).unwrap_or_default())?,
+            Command::Parent { .. } => writeln!(out, "{}", fqdn.parent().map(dotted).unwrap_or_default())?,
+            Command::Hierarchy { .. } => {
+                let hierarchy = fqdn.hierarchy().map(dotted).collect::<Vec<_>>();
+                writeln!(out, "{}", hierarchy.join(" "))?
+            }
+            Command::Sort { .. } => sorted.push(fqdn),
+            Command::Ptr { .. } => unreachable!(),
+        }
+    }
+
+    if let Command::Sort { unique, .. } = cli.command {
+        sorted.sort_unstable_by(|a, b| a.canonical_cmp(b));
+        if unique {
+            sorted.dedup();
+        }
+        sorted.iter().try_for_each(|fqdn| writeln!(out, "{}", dotted(fqdn)))?;
+    }
+    Ok(valid)
+}
+
+fn main() -> ExitCode
+{
+    let cli = Cli::parse();
+    let mut out = BufWriter::new(io::stdout().lock());
+    match run(&cli, io::stdin().lock(), &mut out, &mut io::stderr()).and_then(|valid| out.flush().map(|_| valid)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        // the output was closed early (e.g. piped into `head`)
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("fqdn: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // runs the command line and returns the validity, the output and the errors
+    fn fqdn(args: &[&str], input: &str) -> (bool, String, String)
+    {
+        let cli = Cli::try_parse_from(["fqdn"].iter().chain(args)).unwrap();
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        let valid = run(&cli, input.as_bytes(), &mut out, &mut err).unwrap();
+        (valid, String::from_utf8(out).unwrap(), String::from_utf8(err).unwrap())
+    }
+
+    #[test]
+    fn check()
+    {
+        assert_eq!(fqdn(&["check", "github.com."], ""), (true, "".into(), "".into()));
+        assert_eq!(fqdn(&["check", "-v"], "github.com.\n\ngit@hub.com.\n"),
+                   (false, "github.com.: ok\ngit@hub.com.: invalid char found in FQDN\n".into(), "".into()));
+        assert_eq!(fqdn(&["check", "--policy", "strict", "_sip.example.org.", "example.org"], "").1,
+                   "_sip.example.org.: invalid char found in FQDN\nexample.org: the trailing dot of the FQDN string is missing\n");
+        assert!(fqdn(&["-p", "relaxed", "check", "github.com", "github.com."], "").0);
+    }
+
+    #[test]
+    fn conversions()
+    {
+        assert_eq!(fqdn(&["normalize", "GitHub.COM."], "").1, "github.com.\n");
+        assert_eq!(fqdn(&["-p", "relaxed", "normalize"], "GitHub.COM\n.\n").1, "github.com.\n.\n");
+        assert_eq!(fqdn(&["encode", "Bücher.example."], "").1, "xn--bcher-kva.example.\n");
+        assert_eq!(fqdn(&["decode", "xn--bcher-kva.example."], "").1, "bücher.example.\n");
+        assert_eq!(fqdn(&["wire", "github.com."], "").1, "0667697468756203636f6d00\n");
+
+        let (valid, out, err) = fqdn(&["normalize", "git@hub.com.", "github.com."], "");
+        assert!(!valid);
+        assert_eq!(out, "github.com.\n");
+        assert_eq!(err, "git@hub.com.: invalid char found in FQDN\n");
+    }
+
+    #[test]
+    fn hierarchy()
+    {
+        let names = "www.github.com.\ncom.\n.\n";
+        assert_eq!(fqdn(&["depth"], names).1, "3\n1\n0\n");
+        assert_eq!(fqdn(&["tld"], names).1, "com.\ncom.\n\n");
+        assert_eq!(fqdn(&["parent"], names).1, "github.com.\n\n\n");
+        assert_eq!(fqdn(&["hierarchy"], names).1, "www.github.com. github.com. com.\ncom.\n\n");
+    }
+
+    #[test]
+    fn ptr()
+    {
+        assert_eq!(fqdn(&["ptr", "192.0.2.1"], "").1, "1.2.0.192.in-addr.arpa.\n");
+        assert_eq!(fqdn(&["ptr"], "::1\n").1, format!("1.{}ip6.arpa.\n", "0.".repeat(31)));
+        assert!(!fqdn(&["ptr", "192.0.2"], "").0);
+    }
+
+    #[test]
+    fn sort()
+    {
+        let names = "z.example.\nA.example.\nexample.\na.example.\n";
+        assert_eq!(fqdn(&["sort"], names).1, "example.\na.example.\na.example.\nz.example.\n");
+        assert_eq!(fqdn(&["sort", "-u"], names).1, "example.\na.example.\nz.example.\n");
+    }
+}
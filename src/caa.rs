@@ -0,0 +1,295 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::{try_fqdn, Fqdn, Hierarchy, FQDN};
+
+// the maximal number of aliases followed, as a resolver would do, before giving up
+const MAX_ALIASES: usize = 8;
+
+/// A property of a CAA record (RFC 8659), as `0 issue "letsencrypt.org"`.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let property = "128 issue \"ca.example.net; account=230123\"".parse::<CaaProperty>()?;
+/// assert![ property.is_critical() ];
+/// assert_eq![ property.tag, "issue" ];
+/// assert_eq![ property.issuer(), Some(fqdn!("ca.example.net")) ];
+/// assert_eq![ property.to_string(), "128 issue \"ca.example.net; account=230123\"" ];
+/// # Ok::<(), CaaParseError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CaaProperty {
+    /// The flags of the property (only the critical flag, 128, is defined).
+    pub flags: u8,
+    /// The tag of the property, in lowercase (e.g. `issue`, `issuewild` or `iodef`).
+    pub tag: String,
+    /// The value of the property, without quotes.
+    pub value: String,
+}
+
+impl CaaProperty {
+
+    /// Builds a property (the tag is converted to lowercase).
+    pub fn new(flags: u8, tag: &str, value: &str) -> Self
+    {
+        Self { flags, tag: tag.to_ascii_lowercase(), value: value.into() }
+    }
+
+    /// Checks if the critical flag is set, so that a CA which does not understand
+    /// this property must not issue any certificate.
+    #[inline]
+    pub fn is_critical(&self) -> bool { self.flags & 128 != 0 }
+
+    /// Gets the issuer domain name of an `issue` or `issuewild` property.
+    ///
+    /// `None` is returned if the value has no issuer (as `;`, which forbids any issuance)
+    /// or if the issuer is not a valid FQDN.
+    pub fn issuer(&self) -> Option<FQDN>
+    {
+        let issuer = self.value.split(';').next().unwrap_or_default().trim();
+        if issuer.is_empty() { None } else { try_fqdn!(issuer).ok() }
+    }
+
+    // checks if this property is understood by the evaluator
+    fn is_known(&self) -> bool
+    {
+        matches!(self.tag.as_str(), "issue" | "issuewild" | "iodef" | "issuemail" | "contactemail" | "contactphone")
+    }
+}
+
+impl FromStr for CaaProperty {
+    type Err = CaaParseError;
+
+    /// Parses the presentation format of a CAA record: the flags, the tag and the (quoted) value.
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut fields = s.trim().splitn(3, [' ', '\t']);
+        let flags = fields.next().and_then(|f| f.parse::<u8>().ok()).ok_or(CaaParseError)?;
+        let tag = fields.next().unwrap_or_default();
+        if tag.is_empty() || tag.len() > 15 || !tag.bytes().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(CaaParseError);
+        }
+        let value = fields.next().unwrap_or_default().trim();
+        let value = match value.strip_prefix('"') {
+            None => value,
+            Some(value) => value.strip_suffix('"').ok_or(CaaParseError)?,
+        };
+        Ok(Self::new(flags, tag, value))
+    }
+}
+
+impl fmt::Display for CaaProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        write!(f, "{} {} \"{}\"", self.flags, self.tag, self.value)
+    }
+}
+
+/// Error when a CAA record is not in the presentation format `<flags> <tag> "<value>"`
+/// (e.g. `256 issue "ca.example.net"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CaaParseError;
+
+impl fmt::Display for CaaParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str("invalid CAA record") }
+}
+
+impl core::error::Error for CaaParseError { }
+
+
+/// The CAA records of some domains, used to check if a CA is allowed to issue a certificate.
+///
+/// The relevant record set of a domain is found by climbing its [lookup order](Fqdn::caa_lookup_order)
+/// up to the first domain which has CAA records (RFC 8659, section 3). As a DNS query would do,
+/// the aliases (CNAME) are followed, but only the records of the target itself are used.
+///
+/// A wildcard name as `*.example.com` is only checked through the `wildcard` flag
+/// of [`CaaRecords::is_issuance_allowed`].
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let mut records = CaaRecords::new();
+/// records.insert(fqdn!("example.com"), vec![
+///     "0 issue \"ca.example.net\"".parse()?,
+///     "0 issuewild \";\"".parse()?,
+/// ]);
+/// let www = fqdn!("www.example.com");
+/// let (owner, set) = records.relevant_set(&www).unwrap();
+/// assert_eq![ owner, fqdn!("example.com").as_ref() ];
+/// assert_eq![ set.len(), 2 ];
+///
+/// let ca = fqdn!("ca.example.net");
+/// assert![ records.is_issuance_allowed(&www, false, &ca) ];
+/// assert![ ! records.is_issuance_allowed(&fqdn!("example.com"), true, &ca) ];
+/// assert![ ! records.is_issuance_allowed(&www, false, &fqdn!("other-ca.example")) ];
+/// assert![ records.is_issuance_allowed(&fqdn!("example.org"), false, &fqdn!("other-ca.example")) ];
+/// # Ok::<(), CaaParseError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaaRecords {
+    records: BTreeMap<FQDN, Vec<CaaProperty>>,
+    aliases: BTreeMap<FQDN, FQDN>,
+}
+
+impl CaaRecords {
+
+    /// Builds an empty set of records (which allows any issuance).
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Sets the CAA properties of a domain.
+    #[inline]
+    pub fn insert(&mut self, fqdn: FQDN, properties: Vec<CaaProperty>)
+    {
+        self.records.insert(fqdn, properties);
+    }
+
+    /// Declares that a domain is an alias (CNAME) of another one.
+    #[inline]
+    pub fn insert_alias(&mut self, alias: FQDN, target: FQDN)
+    {
+        self.aliases.insert(alias, target);
+    }
+
+    /// Gets the CAA properties of a domain, following its aliases.
+    pub fn get(&self, fqdn: &Fqdn) -> &[CaaProperty]
+    {
+        let mut fqdn = fqdn;
+        for _ in 0..=MAX_ALIASES {
+            match self.aliases.get(fqdn) {
+                Some(target) => fqdn = target,
+                None => return self.records.get(fqdn).map_or(&[], Vec::as_slice),
+            }
+        }
+        // too many aliases (or a loop), as a resolver would fail
+        &[]
+    }
+
+    /// Finds the relevant record set of a domain, with the domain of the lookup order which has it.
+    ///
+    /// `None` is returned if there is no CAA record up to the TLD, then any CA could issue a certificate.
+    pub fn relevant_set<'a>(&'a self, fqdn: &'a Fqdn) -> Option<(&'a Fqdn, &'a [CaaProperty])>
+    {
+        fqdn.caa_lookup_order()
+            .map(|fqdn| (fqdn, self.get(fqdn)))
+            .find(|(_, set)| !set.is_empty())
+    }
+
+    /// Checks if a CA, identified by its issuer domain name, could issue a certificate for a domain
+    /// (or for the wildcard name `*.fqdn` if `wildcard` is set).
+    ///
+    /// Since `*` is not a valid label, the `wildcard` flag is the only way to check a wildcard name:
+    /// the relevant record set is the one of `fqdn` (e.g. `example.com` for `*.example.com`)
+    /// and its `issuewild` properties, if any, take precedence over the `issue` ones.
+    ///
+    /// The issuance is forbidden if the relevant record set has a critical property that is not understood,
+    /// or if it has `issue` properties (or `issuewild` ones for a wildcard) but none of them names the issuer.
+    pub fn is_issuance_allowed(&self, fqdn: &Fqdn, wildcard: bool, issuer: &Fqdn) -> bool
+    {
+        let Some((_, set)) = self.relevant_set(fqdn) else { return true };
+        if set.iter().any(|p| p.is_critical() && !p.is_known()) {
+            return false;
+        }
+        // without `issuewild`, the `issue` properties apply to wildcard names too
+        let tag = if wildcard && set.iter().any(|p| p.tag == "issuewild") { "issuewild" } else { "issue" };
+        let mut properties = set.iter().filter(|p| p.tag == tag).peekable();
+        if properties.peek().is_none() {
+            return true;
+        }
+        properties.any(|p| p.issuer().as_deref() == Some(issuer))
+    }
+}
+
+impl FromIterator<(FQDN, Vec<CaaProperty>)> for CaaRecords {
+    fn from_iter<I: IntoIterator<Item = (FQDN, Vec<CaaProperty>)>>(iter: I) -> Self
+    {
+        Self { records: iter.into_iter().collect(), aliases: BTreeMap::new() }
+    }
+}
+
+impl From<BTreeMap<FQDN, Vec<CaaProperty>>> for CaaRecords {
+    #[inline]
+    fn from(records: BTreeMap<FQDN, Vec<CaaProperty>>) -> Self { Self { records, aliases: BTreeMap::new() } }
+}
+
+impl Fqdn {
+
+    /// Iterates over the domains whose CAA records are looked up, in order, to find the
+    /// relevant record set of this FQDN: from the FQDN itself up to its TLD (RFC 8659, section 3).
+    ///
+    /// See [`CaaRecords`] to evaluate the records.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let fqdn = fqdn!("www.example.com");
+    /// assert![ fqdn.caa_lookup_order().eq([fqdn!("www.example.com"), fqdn!("example.com"), fqdn!("com")].iter().map(|f| f.as_ref())) ];
+    /// ```
+    #[inline]
+    pub fn caa_lookup_order(&self) -> Hierarchy<'_> { self.hierarchy() }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    fn property(s: &str) -> CaaProperty { s.parse().unwrap() }
+
+    #[test]
+    fn properties()
+    {
+        assert_eq!(property("0 ISSUE ca.example.net"), CaaProperty::new(0, "issue", "ca.example.net"));
+        assert_eq!(property("0 issue \";\"").issuer(), None);
+        assert_eq!(property("0 issue \"\"").issuer(), None);
+        assert_eq!(property("0 issue \"CA.example.net.\"").issuer(), Some(fqdn!("ca.example.net")));
+        assert_eq!(property("0 iodef \"mailto:security@example.com\"").value, "mailto:security@example.com");
+
+        for invalid in ["", "256 issue \"ca\"", "x issue \"ca\"", "0", "0 is-sue \"ca\"", "0 issue \"ca", "0 averyveryverylongtag \"\""] {
+            assert_eq!(invalid.parse::<CaaProperty>(), Err(CaaParseError), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn evaluation()
+    {
+        let ca = fqdn!("ca.example.net");
+        let other = fqdn!("other.example.net");
+        let mut records = [
+            (fqdn!("example.com"), vec![property("0 issue \"ca.example.net\""), property("0 iodef \"mailto:a@example.com\"")]),
+            (fqdn!("forbidden.example.com"), vec![property("0 issue \";\"")]),
+            (fqdn!("wild.example.com"), vec![property("0 issue \"other.example.net\""), property("0 issuewild \"ca.example.net\"")]),
+            (fqdn!("critical.example.com"), vec![property("128 tbs \"unknown\""), property("0 issue \"ca.example.net\"")]),
+            (fqdn!("iodef.example.com"), vec![property("0 iodef \"mailto:a@example.com\"")]),
+            (fqdn!("target.example.org"), vec![property("0 issue \"other.example.net\"")]),
+        ].into_iter().collect::<CaaRecords>();
+
+        assert!(records.is_issuance_allowed(&fqdn!("a.b.example.com"), false, &ca));
+        assert!(records.is_issuance_allowed(&fqdn!("example.com"), true, &ca));
+        assert!(!records.is_issuance_allowed(&fqdn!("example.com"), false, &other));
+        assert!(!records.is_issuance_allowed(&fqdn!("www.forbidden.example.com"), false, &ca));
+        assert!(records.is_issuance_allowed(&fqdn!("wild.example.com"), true, &ca));
+        assert!(!records.is_issuance_allowed(&fqdn!("wild.example.com"), false, &ca));
+        assert!(!records.is_issuance_allowed(&fqdn!("critical.example.com"), false, &ca));
+        assert!(records.is_issuance_allowed(&fqdn!("iodef.example.com"), false, &other));
+        assert!(records.is_issuance_allowed(&fqdn!("example.org"), false, &other));
+        assert_eq!(records.relevant_set(&fqdn!("example.org")), None);
+
+        // aliases: the records of the target are used, but not those of its parents
+        records.insert_alias(fqdn!("cdn.example.com"), fqdn!("target.example.org"));
+        records.insert_alias(fqdn!("www.cdn.example.com"), fqdn!("cdn.example.org"));
+        assert!(records.is_issuance_allowed(&fqdn!("cdn.example.com"), false, &other));
+        assert!(!records.is_issuance_allowed(&fqdn!("cdn.example.com"), false, &ca));
+        assert_eq!(records.relevant_set(&fqdn!("www.cdn.example.com")).map(|(owner, _)| owner), Some(fqdn!("cdn.example.com").as_ref()));
+
+        // alias loops are ignored
+        records.insert_alias(fqdn!("loop.example.org"), fqdn!("loop.example.org"));
+        assert!(records.get(&fqdn!("loop.example.org")).is_empty());
+    }
+}
@@ -0,0 +1,399 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Bound;
+use std::time::{Duration, Instant};
+
+use crate::{Fqdn, FQDN};
+
+/// A source of time for a [`FqdnCache`].
+///
+/// The time is a duration since an arbitrary (but fixed) origin, so that a test could
+/// provide its own clock (any `Fn() -> Duration` is a clock) instead of waiting for entries to expire.
+pub trait Clock {
+    /// Gets the current time.
+    fn now(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> Clock for F {
+    #[inline]
+    fn now(&self) -> Duration { self() }
+}
+
+/// The monotonic clock of the system, used by default by [`FqdnCache`].
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock(Instant);
+
+impl Default for SystemClock {
+    #[inline]
+    fn default() -> Self { Self(Instant::now()) }
+}
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Duration { self.0.elapsed() }
+}
+
+/// The result of a lookup in a [`FqdnCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheLookup<'a, V> {
+    /// The FQDN is cached with this value.
+    Hit(&'a V),
+    /// The FQDN does not exist since this domain (the FQDN itself or one of its ancestors)
+    /// is known to not exist (RFC 8020).
+    NxDomain(&'a Fqdn),
+    /// Nothing is known about the FQDN.
+    Miss,
+}
+
+struct Entry<V> {
+    value: V,
+    expires: Duration,
+}
+
+// A FQDN sorted in the canonical order of DNS names (RFC 4034), so that a domain
+// and all its subdomains are contiguous in a map
+#[repr(transparent)]
+struct Canonical(Fqdn);
+
+impl Canonical {
+    #[inline]
+    fn new(fqdn: &Fqdn) -> &Self
+    {
+        // it is safe since Canonical is a transparent wrapper of Fqdn
+        unsafe { &*(fqdn as *const Fqdn as *const Canonical) }
+    }
+}
+
+impl PartialEq for Canonical {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+}
+
+impl Eq for Canonical { }
+
+impl PartialOrd for Canonical {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for Canonical {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering { self.0.canonical_cmp(&other.0) }
+}
+
+// The owned key of the maps of the cache, which borrows as a Canonical to be looked up
+// (so it should be sorted in the same order)
+struct CanonicalKey(FQDN);
+
+impl Borrow<Canonical> for CanonicalKey {
+    #[inline]
+    fn borrow(&self) -> &Canonical { Canonical::new(&self.0) }
+}
+
+impl PartialEq for CanonicalKey {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { self.0 == other.0 }
+}
+
+impl Eq for CanonicalKey { }
+
+impl PartialOrd for CanonicalKey {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for CanonicalKey {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering { self.0.canonical_cmp(&other.0) }
+}
+
+// Removes all the entries of a domain and its subdomains, which are contiguous in the canonical order
+fn remove_subtree<T>(map: &mut BTreeMap<CanonicalKey, T>, zone: &Fqdn)
+{
+    let subtree = map.range::<Canonical, _>((Bound::Included(Canonical::new(zone)), Bound::Unbounded))
+        .map(|(name, _)| name)
+        .take_while(|name| name.0.is_subdomain_of(zone))
+        .map(|name| name.0.clone())
+        .collect::<Vec<_>>();
+    for name in subtree {
+        map.remove(Canonical::new(&name));
+    }
+}
+
+/// A cache of values associated to FQDN, which expire after their TTL.
+///
+/// Following the RFC 8020, a cached NXDOMAIN answer for a domain implies that none of its
+/// subdomains exist: while it is not expired, looking up any of them returns
+/// [`CacheLookup::NxDomain`]. A whole subtree could also be flushed at once.
+///
+/// Expired entries are ignored by the lookups and are removed by [`FqdnCache::purge_expired`].
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// use std::cell::Cell;
+/// use std::time::Duration;
+///
+/// let now = Cell::new(Duration::ZERO);
+/// let mut cache = FqdnCache::with_clock(|| now.get());
+///
+/// cache.insert(fqdn!("www.example.com"), "192.0.2.1", Duration::from_secs(300));
+/// cache.insert_nxdomain(fqdn!("b.example.com"), Duration::from_secs(60));
+///
+/// assert_eq![ cache.get(&fqdn!("www.example.com")), CacheLookup::Hit(&"192.0.2.1") ];
+/// assert_eq![ cache.get(&fqdn!("a.b.example.com")), CacheLookup::NxDomain(fqdn!("b.example.com").as_ref()) ];
+///
+/// now.set(Duration::from_secs(120));
+/// assert_eq![ cache.get(&fqdn!("a.b.example.com")), CacheLookup::Miss ];
+///
+/// cache.flush_subtree(&fqdn!("example.com"));
+/// assert_eq![ cache.get(&fqdn!("www.example.com")), CacheLookup::Miss ];
+/// ```
+pub struct FqdnCache<V, C = SystemClock> {
+    entries: BTreeMap<CanonicalKey, Entry<V>>,
+    nxdomains: BTreeMap<CanonicalKey, Duration>,
+    clock: C,
+}
+
+impl<V> Default for FqdnCache<V> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+
+impl<V> FqdnCache<V> {
+
+    /// Creates an empty cache using the system clock.
+    #[inline]
+    pub fn new() -> Self { Self::with_clock(SystemClock::default()) }
+}
+
+impl<V, C: Clock> FqdnCache<V, C> {
+
+    /// Creates an empty cache using the given clock.
+    #[inline]
+    pub fn with_clock(clock: C) -> Self
+    {
+        Self { entries: BTreeMap::new(), nxdomains: BTreeMap::new(), clock }
+    }
+
+    /// Caches a value for a FQDN, replacing the previous one.
+    ///
+    /// Since the FQDN exists, the NXDOMAIN answers of the FQDN and of its ancestors are forgotten.
+    pub fn insert(&mut self, fqdn: FQDN, value: V, ttl: Duration)
+    {
+        if !self.nxdomains.is_empty() {
+            // the hierarchy excludes the top domain
+            for name in fqdn.hierarchy().chain(fqdn.suffix(0)) {
+                self.nxdomains.remove(Canonical::new(name));
+            }
+        }
+        let expires = self.clock.now().saturating_add(ttl);
+        self.entries.insert(CanonicalKey(fqdn), Entry { value, expires });
+    }
+
+    /// Caches the non-existence of a FQDN and, so, of all its subdomains.
+    ///
+    /// The values cached for the FQDN or its subdomains are removed.
+    pub fn insert_nxdomain(&mut self, fqdn: FQDN, ttl: Duration)
+    {
+        remove_subtree(&mut self.entries, &fqdn);
+        remove_subtree(&mut self.nxdomains, &fqdn);
+        let expires = self.clock.now().saturating_add(ttl);
+        self.nxdomains.insert(CanonicalKey(fqdn), expires);
+    }
+
+    /// Looks up a FQDN.
+    ///
+    /// The non-existence of an ancestor takes precedence over the value of the FQDN.
+    pub fn get(&self, fqdn: &Fqdn) -> CacheLookup<'_, V>
+    {
+        let now = self.clock.now();
+        if !self.nxdomains.is_empty() {
+            // from the top domain, since the hierarchy excludes it
+            let cut = fqdn.suffix(0).into_iter()
+                .chain(fqdn.hierarchy().rev())
+                .find_map(|name| self.nxdomains.get_key_value(Canonical::new(name)).filter(|(_, &expires)| expires > now));
+            if let Some((cut, _)) = cut {
+                return CacheLookup::NxDomain(&cut.0);
+            }
+        }
+        match self.entries.get(Canonical::new(fqdn)) {
+            Some(entry) if entry.expires > now => CacheLookup::Hit(&entry.value),
+            _ => CacheLookup::Miss,
+        }
+    }
+
+    /// Gets the remaining time to live of the value of a FQDN, if it is cached.
+    pub fn ttl(&self, fqdn: &Fqdn) -> Option<Duration>
+    {
+        let now = self.clock.now();
+        self.entries.get(Canonical::new(fqdn))
+            .and_then(|entry| entry.expires.checked_sub(now))
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Removes the value of a FQDN and returns it, if it was not expired.
+    pub fn remove(&mut self, fqdn: &Fqdn) -> Option<V>
+    {
+        let now = self.clock.now();
+        self.entries.remove(Canonical::new(fqdn)).filter(|entry| entry.expires > now).map(|entry| entry.value)
+    }
+
+    /// Removes everything cached for a domain and all its subdomains (values and NXDOMAIN answers)
+    /// and returns the number of removed entries.
+    ///
+    /// Flushing the top domain clears the whole cache.
+    pub fn flush_subtree(&mut self, zone: &Fqdn) -> usize
+    {
+        let len = self.entries.len() + self.nxdomains.len();
+        remove_subtree(&mut self.entries, zone);
+        remove_subtree(&mut self.nxdomains, zone);
+        len - self.entries.len() - self.nxdomains.len()
+    }
+
+    /// Removes the expired entries and returns how many they were.
+    pub fn purge_expired(&mut self) -> usize
+    {
+        let now = self.clock.now();
+        let len = self.entries.len() + self.nxdomains.len();
+        self.entries.retain(|_, entry| entry.expires > now);
+        self.nxdomains.retain(|_, &mut expires| expires > now);
+        len - self.entries.len() - self.nxdomains.len()
+    }
+
+    /// Counts the cached entries (values and NXDOMAIN answers), including the expired ones
+    /// which are not purged yet.
+    #[inline]
+    pub fn len(&self) -> usize { self.entries.len() + self.nxdomains.len() }
+
+    /// Checks if the cache is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Removes all the entries.
+    #[inline]
+    pub fn clear(&mut self)
+    {
+        self.entries.clear();
+        self.nxdomains.clear();
+    }
+}
+
+impl<V, C> fmt::Debug for FqdnCache<V, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_struct("FqdnCache")
+            .field("entries", &self.entries.len())
+            .field("nxdomains", &self.nxdomains.len())
+            .finish()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::Duration;
+    use crate as fqdn;
+    use fqdn::*;
+
+    const fn secs(s: u64) -> Duration { Duration::from_secs(s) }
+
+    #[test]
+    fn expiration()
+    {
+        let now = Cell::new(secs(1000));
+        let mut cache = FqdnCache::with_clock(|| now.get());
+        cache.insert(fqdn!("a.example.com"), 1, secs(10));
+        cache.insert(fqdn!("b.example.com"), 2, secs(20));
+        assert_eq!(cache.get(&fqdn!("a.example.com")), CacheLookup::Hit(&1));
+        assert_eq!(cache.get(&fqdn!("A.Example.com")), CacheLookup::Hit(&1));
+        assert_eq!(cache.get(&fqdn!("example.com")), CacheLookup::Miss);
+        assert_eq!(cache.ttl(&fqdn!("b.example.com")), Some(secs(20)));
+
+        now.set(secs(1010));
+        assert_eq!(cache.get(&fqdn!("a.example.com")), CacheLookup::Miss);
+        assert_eq!(cache.ttl(&fqdn!("a.example.com")), None);
+        assert_eq!(cache.ttl(&fqdn!("b.example.com")), Some(secs(10)));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.purge_expired(), 1);
+        assert_eq!(cache.remove(&fqdn!("b.example.com")), Some(2));
+        assert!(cache.is_empty());
+
+        // refreshed entry
+        cache.insert(fqdn!("a.example.com"), 3, secs(5));
+        cache.insert(fqdn!("a.example.com"), 4, secs(50));
+        now.set(secs(1030));
+        assert_eq!(cache.get(&fqdn!("a.example.com")), CacheLookup::Hit(&4));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn nxdomain()
+    {
+        let now = Cell::new(secs(0));
+        let mut cache = FqdnCache::with_clock(|| now.get());
+        cache.insert(fqdn!("x.b.example.com"), "x", secs(100));
+        cache.insert(fqdn!("c.example.com"), "c", secs(100));
+        cache.insert_nxdomain(fqdn!("a.b.example.com"), secs(30));
+        cache.insert_nxdomain(fqdn!("b.example.com"), secs(10));
+
+        // the subdomains are removed, the deepest cut is hidden by its ancestor
+        assert_eq!(cache.len(), 2);
+        let b = fqdn!("b.example.com");
+        assert_eq!(cache.get(&fqdn!("x.b.example.com")), CacheLookup::NxDomain(&b));
+        assert_eq!(cache.get(&fqdn!("b.example.com")), CacheLookup::NxDomain(&b));
+        assert_eq!(cache.get(&fqdn!("c.example.com")), CacheLookup::Hit(&"c"));
+        assert_eq!(cache.get(&fqdn!("example.com")), CacheLookup::Miss);
+
+        // a nested cut still applies when its ancestor expires
+        cache.insert_nxdomain(fqdn!("z.y.b.example.com"), secs(60));
+        now.set(secs(20));
+        assert_eq!(cache.get(&fqdn!("w.z.y.b.example.com")), CacheLookup::NxDomain(&fqdn!("z.y.b.example.com")));
+        assert_eq!(cache.get(&fqdn!("y.b.example.com")), CacheLookup::Miss);
+
+        // a positive answer removes the cuts above it
+        cache.insert(fqdn!("v.z.y.b.example.com"), "v", secs(100));
+        assert_eq!(cache.get(&fqdn!("v.z.y.b.example.com")), CacheLookup::Hit(&"v"));
+        assert_eq!(cache.get(&fqdn!("w.z.y.b.example.com")), CacheLookup::Miss);
+
+        // the top domain
+        cache.insert_nxdomain(FQDN::default(), secs(10));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&fqdn!("c.example.com")), CacheLookup::NxDomain(&FQDN::default()));
+    }
+
+    #[test]
+    fn flush()
+    {
+        let mut cache = FqdnCache::new();
+        for name in ["example.com", "a.example.com", "b.a.example.com", "example.org", "com"] {
+            cache.insert(try_fqdn!(name).unwrap(), name, secs(60));
+        }
+        cache.insert_nxdomain(fqdn!("c.example.com"), secs(60));
+        assert_eq!(cache.flush_subtree(&fqdn!("a.example.com")), 2);
+        assert_eq!(cache.flush_subtree(&fqdn!("a.example.com")), 0);
+        assert_eq!(cache.flush_subtree(&fqdn!("example.com")), 2);
+        assert_eq!(cache.get(&fqdn!("example.org")), CacheLookup::Hit(&"example.org"));
+        assert_eq!(cache.flush_subtree(&FQDN::default()), 2);
+        assert!(cache.is_empty());
+        assert_eq!(format!("{cache:?}"), "FqdnCache { entries: 0, nxdomains: 0 }");
+
+        // the neighbours of a subtree in the canonical order are kept
+        let names = ["a.example", "b.example", "x.b.example", "y.x.b.example", "b-x.example", "bb.example", "b.example.org", "c.example"];
+        for name in names {
+            cache.insert(try_fqdn!(name).unwrap(), name, secs(60));
+        }
+        cache.insert_nxdomain(fqdn!("z.b.example"), secs(60));
+        assert_eq!(cache.flush_subtree(&fqdn!("b.example")), 4);
+        for name in names {
+            let kept = !try_fqdn!(name).unwrap().is_subdomain_of(&fqdn!("b.example"));
+            assert_eq!(cache.get(&try_fqdn!(name).unwrap()) != CacheLookup::Miss, kept, "{name}");
+        }
+        cache.insert_nxdomain(fqdn!("example"), secs(60));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&fqdn!("b.example.org")), CacheLookup::Hit(&"b.example.org"));
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{try_fqdn, Fqdn, FQDN};
+
+/// The rules to match a FQDN against the DNS names of a certificate (RFC 6125, section 6.4).
+///
+/// By default, the rules of the CA/Browser Forum apply:
+/// * the wildcard `*` is only allowed as the complete leftmost label (as `*.example.com`),
+/// * it matches exactly one label (so neither `example.com` nor `a.b.example.com` match `*.example.com`),
+/// * it could not cover a whole public suffix (as `*.com`),
+/// * the internationalized names are compared through their A-labels (`xn--...`), and a wildcard
+///   never matches a part of an A-label.
+///
+/// Since this crate has no list of public suffixes, only the top level domains are considered
+/// as public suffixes by default: a complete list could be plugged in with `is_public_suffix`.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let fqdn = fqdn!("foo.example.com");
+/// let matcher = CertificateNameMatcher { partial_wildcards: true, ..Default::default() };
+/// assert![ matcher.matches(&fqdn, "f*.example.com") ];
+/// assert![ ! CertificateNameMatcher::default().matches(&fqdn, "f*.example.com") ];
+///
+/// let matcher = CertificateNameMatcher {
+///     partial_wildcards: false,
+///     is_public_suffix: |suffix: &Fqdn| suffix.depth() < 2 || suffix == fqdn!("co.uk").as_ref(),
+/// };
+/// assert![ ! matcher.matches(&fqdn!("example.co.uk"), "*.co.uk") ];
+/// assert![ matcher.matches(&fqdn!("www.example.co.uk"), "*.example.co.uk") ];
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CertificateNameMatcher<P = fn(&Fqdn) -> bool> {
+    /// Allows a wildcard as a part of the leftmost label (as `f*.example.com` or `*-db.example.com`),
+    /// as the RFC 6125 does but not the CA/Browser Forum.
+    pub partial_wildcards: bool,
+    /// Checks if a domain is a public suffix, on which a wildcard is forbidden.
+    pub is_public_suffix: P,
+}
+
+impl Default for CertificateNameMatcher {
+    /// Forbids partial wildcards and wildcards on top level domains.
+    #[inline]
+    fn default() -> Self
+    {
+        Self { partial_wildcards: false, is_public_suffix: |suffix| suffix.depth() < 2 }
+    }
+}
+
+impl<P: Fn(&Fqdn) -> bool> CertificateNameMatcher<P> {
+
+    /// Checks if a FQDN matches a DNS name of a certificate (the trailing dot of the name is optional).
+    pub fn matches(&self, fqdn: &Fqdn, pattern: &str) -> bool
+    {
+        let pattern = pattern.strip_suffix('.').unwrap_or(pattern);
+        if pattern.is_empty() || pattern.ends_with('.') {
+            return false;
+        }
+        let Some((first, rest)) = pattern.split_once('.') else {
+            // a single label, which could not be a wildcard
+            return !pattern.contains('*') && parse(pattern).is_some_and(|name| name.as_ref() == fqdn);
+        };
+        if rest.contains('*') {
+            return false;
+        }
+        if !first.contains('*') {
+            return parse(pattern).is_some_and(|name| name.as_ref() == fqdn);
+        }
+
+        // the wildcard label
+        let Some(parent) = parse(rest) else { return false };
+        if (self.is_public_suffix)(&parent) || fqdn.parent() != Some(parent.as_ref()) {
+            return false;
+        }
+        if first == "*" {
+            return true;
+        }
+        let label = fqdn.labels().next().unwrap_or_default();
+        let first = first.to_ascii_lowercase();
+        let (prefix, suffix) = first.split_once('*').unwrap_or_default();
+        self.partial_wildcards
+            && first.is_ascii()
+            && !suffix.contains('*')
+            && !first.starts_with("xn--")
+            && !label.starts_with("xn--")
+            && label.len() >= prefix.len() + suffix.len()
+            && label.starts_with(prefix)
+            && label.ends_with(suffix)
+    }
+
+    /// Checks if a FQDN matches any of the DNS names of a certificate (as the `dNSName` entries
+    /// of its subject alternative names).
+    pub fn matches_any<'a>(&self, fqdn: &Fqdn, patterns: impl IntoIterator<Item = &'a str>) -> bool
+    {
+        patterns.into_iter().any(|pattern| self.matches(fqdn, pattern))
+    }
+}
+
+// Parses a name of a certificate, converting its U-labels to A-labels if the feature `punycode` is activated
+#[inline]
+fn parse(name: &str) -> Option<FQDN> { try_fqdn!(name).ok() }
+
+impl Fqdn {
+
+    /// Checks if this FQDN matches a DNS name of a certificate, following the rules
+    /// of the CA/Browser Forum (see [`CertificateNameMatcher`] for the details).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let fqdn = fqdn!("www.example.com");
+    /// assert![ fqdn.matches_certificate_name("*.example.com") ];
+    /// assert![ fqdn.matches_certificate_name("WWW.Example.com.") ];
+    /// assert![ ! fqdn.matches_certificate_name("*.com") ];
+    /// assert![ ! fqdn.matches_certificate_name("*.www.example.com") ];
+    /// assert![ ! fqdn.matches_certificate_name("www.*.com") ];
+    /// ```
+    #[inline]
+    pub fn matches_certificate_name(&self, pattern: &str) -> bool
+    {
+        CertificateNameMatcher::default().matches(self, pattern)
+    }
+
+    /// Checks if this FQDN matches any of the DNS names of a certificate (as the `dNSName` entries
+    /// of its subject alternative names).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let sans = ["example.com", "*.example.com"];
+    /// assert![ fqdn!("example.com").matches_certificate_names(sans) ];
+    /// assert![ fqdn!("api.example.com").matches_certificate_names(sans) ];
+    /// assert![ ! fqdn!("v1.api.example.com").matches_certificate_names(sans) ];
+    /// ```
+    #[inline]
+    pub fn matches_certificate_names<'a>(&self, patterns: impl IntoIterator<Item = &'a str>) -> bool
+    {
+        CertificateNameMatcher::default().matches_any(self, patterns)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn wildcards()
+    {
+        let fqdn = fqdn!("foo.example.com");
+        assert!(fqdn.matches_certificate_name("*.example.com"));
+        assert!(fqdn.matches_certificate_name("*.example.com."));
+        assert!(!fqdn.matches_certificate_name("*.*.com"));
+        assert!(!fqdn.matches_certificate_name("*"));
+        assert!(!fqdn.matches_certificate_name("**.example.com"));
+        assert!(!fqdn.matches_certificate_name("*.foo.example.com"));
+        assert!(!fqdn!("example.com").matches_certificate_name("*.example.com"));
+        assert!(!fqdn!("a.foo.example.com").matches_certificate_name("*.example.com"));
+        assert!(!fqdn!("com").matches_certificate_name("*"));
+        assert!(!fqdn!("example.com").matches_certificate_name("*.com"));
+        assert!(!fqdn.matches_certificate_name("*..com"));
+        assert!(!fqdn.matches_certificate_name(""));
+
+        // partial wildcards
+        for pattern in ["f*.example.com", "*o.example.com", "f*o.example.com", "*oo.example.com"] {
+            assert!(!fqdn.matches_certificate_name(pattern), "{pattern}");
+        }
+        let matcher = CertificateNameMatcher { partial_wildcards: true, ..Default::default() };
+        for pattern in ["f*.example.com", "*o.example.com", "F*O.example.com", "*oo.example.com", "*foo.example.com"] {
+            assert!(matcher.matches(&fqdn, pattern), "{pattern}");
+        }
+        for pattern in ["b*.example.com", "fo*oo.example.com", "f**.example.com", "f*.com", "f*.*.com"] {
+            assert!(!matcher.matches(&fqdn, pattern), "{pattern}");
+        }
+        assert!(!matcher.matches(&fqdn!("xn--bcher-kva.example.com"), "xn--b*.example.com"));
+        assert!(!matcher.matches(&fqdn!("xn--bcher-kva.example.com"), "x*.example.com"));
+        assert!(matcher.matches(&fqdn!("xn--bcher-kva.example.com"), "*.example.com"));
+    }
+
+    #[test]
+    fn names()
+    {
+        let fqdn = fqdn!("www.example.com");
+        assert!(fqdn.matches_certificate_name("www.example.com"));
+        assert!(fqdn.matches_certificate_name("www.EXAMPLE.com."));
+        assert!(!fqdn.matches_certificate_name("example.com"));
+        assert!(!fqdn.matches_certificate_name("www.example.com.."));
+        assert!(!fqdn.matches_certificate_name("w@w.example.com"));
+        assert!(fqdn!("localhost").matches_certificate_name("localhost"));
+        assert!(!fqdn!("localhost").matches_certificate_name("local*"));
+
+        assert!(fqdn.matches_certificate_names(["example.org", "*.example.com"]));
+        assert!(!fqdn.matches_certificate_names(["example.org", "example.com"]));
+        assert!(!fqdn.matches_certificate_names([]));
+    }
+
+    #[cfg(feature = "punycode")]
+    #[test]
+    fn idn()
+    {
+        let fqdn = FQDN::punyencode("www.bücher.example.").unwrap();
+        assert!(fqdn.matches_certificate_name("www.xn--bcher-kva.example"));
+        assert!(fqdn.matches_certificate_name("*.xn--bcher-kva.example"));
+        assert!(fqdn.matches_certificate_name("*.Bücher.example"));
+        assert!(!fqdn.matches_certificate_name("*.bucher.example"));
+    }
+}
@@ -1,7 +1,10 @@
 
 
 /// Error when FQDN parsing goes wrong
+///
+/// New kinds of error could be added in further versions, so a `match` on it needs a wildcard arm.
 #[derive(Debug,Clone,Copy,PartialEq,Eq,Hash)]
+#[non_exhaustive]
 pub enum Error {
 
     /// The trailing dot of the FQDN string is missing.
@@ -51,34 +54,66 @@ pub enum Error {
     LabelCannotEndWithHyphen,
 
     /// One label is empty (e.g. starting dot as `.github.com.` or two following dots as `github..com.`)
-    EmptyLabel
+    EmptyLabel,
+
+    /// The host of an URL or of a `Host` header is an IP address instead of a domain name
+    /// (e.g. `http://127.0.0.1/` or `[::1]:8080`).
+    IpAddressLiteral,
+
+    /// A host looks like an IP address but is not a valid one (e.g. `1.2.3.256` or `[::1`).
+    InvalidIpAddress,
+
+    /// The port following the host is not a valid port number.
+    InvalidPort,
+
+    /// The top level domain is all-numeric (e.g. `github.123.`), which is forbidden by the RFC 3696
+    /// (see the feature `domain-tld-cannot-be-all-numeric`).
+    NumericTopLevelDomain,
+
+    /// There is no host at all (e.g. an URL as `mailto:user@github.com` or an empty `Host` header).
+    MissingHost,
 }
 
-impl std::error::Error for Error { }
+impl core::error::Error for Error { }
 
-use std::fmt;
-use std::fmt::Debug;
+use core::fmt;
+use core::fmt::Debug;
+
+impl Error {
+
+    // the reason of the error, usable in const contexts
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            Error::TrailingDotMissing => "the trailing dot of the FQDN string is missing",
+            Error::TrailingNulCharMissing => "the trailing nul byte of the FQDN bytes is missing",
+            Error::InvalidLabelChar => "invalid char found in FQDN",
+            Error::InvalidStructure => "invalid FQDN byte sequence",
+            Error::TooLongDomainName => "too long FQDN",
+            Error::TooLongLabel => "too long label found in FQDN",
+            Error::LabelCannotStartWithHyphen => "FQDN label can’t start with a hyphen",
+            Error::LabelCannotEndWithHyphen => "FQDN label can’t end with a hyphen",
+            Error::EmptyLabel => "empty label found in FQDN",
+            Error::IpAddressLiteral => "IP address found instead of a FQDN",
+            Error::InvalidIpAddress => "invalid IP address",
+            Error::InvalidPort => "invalid port number",
+            Error::NumericTopLevelDomain => "all-numeric top level domain found in FQDN",
+            Error::MissingHost => "no host found",
+        }
+    }
+}
 
 impl fmt::Display for Error {
 
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(
-            match self {
-                Error::TrailingDotMissing => "the trailing dot of the FQDN string is missing",
-                Error::TrailingNulCharMissing => "the trailing nul byte of the FQDN bytes is missing",
-                Error::InvalidLabelChar => "invalid char found in FQDN",
-                Error::InvalidStructure => "invalid FQDN byte sequence",
-                Error::TooLongDomainName => "too long FQDN",
-                Error::TooLongLabel => "too long label found in FQDN",
-                Error::LabelCannotStartWithHyphen => "FQDN label can’t start with a hyphen",
-                Error::LabelCannotEndWithHyphen => "FQDN label can’t end with a hyphen",
-                Error::EmptyLabel => "empty label found in FQDN",
-            })
+        f.write_str(self.as_str())
     }
 }
 
 // Checks if the bytes are really a FQDN (with lower cases and a trailing nul char)
-pub(crate) fn check_byte_sequence(bytes: &[u8]) -> Result<(),Error>
+//
+// This function is const in order to be able to check FQDN at compile time
+// (so `?` and iterators are not used here)
+pub(crate) const fn check_byte_sequence(bytes: &[u8]) -> Result<(),Error>
 {
     // stop immediately if the trailing nul char is missing
     match bytes.last() {
@@ -96,61 +131,194 @@ pub(crate) fn check_byte_sequence(bytes: &[u8]) -> Result<(),Error>
         return Err(Error::TooLongDomainName)
     }
 
-    let mut iter = bytes.iter();
+    let mut pos = 0;
     let mut remaining = bytes.len() - 1;
 
     while remaining > 0 {
-        match iter.next() {
+        let sublen = bytes[pos] as usize;
+        match sublen {
             // sublen does not match with available bytes
-            None | Some(&0) => return Err(Error::InvalidStructure),
-
-            Some(&sublen) if sublen as usize > remaining => {
-                return Err(Error::InvalidStructure)
-            }
+            0 => return Err(Error::InvalidStructure),
+            _ if sublen > remaining - 1 => return Err(Error::InvalidStructure),
 
             #[cfg(feature="domain-label-length-limited-to-63")]
-            Some(&sublen) if sublen > 63 => {
-                return Err(Error::TooLongLabel)
-            }
+            _ if sublen > 63 => return Err(Error::TooLongLabel),
 
-            #[cfg(feature="domain-label-cannot-start-or-end-with-hyphen")]
-            Some(&1) => { // label with only one single char
-                if check_any_char(*iter.next().unwrap())? == b'-' {
-                    return Err(Error::LabelCannotStartWithHyphen);
-                }
-                remaining -= 2;
-            }
-
-            #[cfg(feature="domain-label-cannot-start-or-end-with-hyphen")]
-            Some(&sublen) => {
-                if check_any_char(*iter.next().unwrap())? == b'-' {
-                    return Err(Error::LabelCannotStartWithHyphen);
-                }
-                for _ in 1..sublen - 1 {
-                    check_any_char(*iter.next().unwrap())?;
+            _ => {
+                let mut i = pos + 1;
+                while i <= pos + sublen {
+                    if let Err(e) = check_any_char(bytes[i]) {
+                        return Err(e);
+                    }
+                    i += 1;
                 }
-                if check_any_char(*iter.next().unwrap())? == b'-' {
-                    return Err(Error::LabelCannotEndWithHyphen);
+                #[cfg(feature="domain-label-cannot-start-or-end-with-hyphen")]
+                {
+                    if bytes[pos + 1] == b'-' {
+                        return Err(Error::LabelCannotStartWithHyphen);
+                    }
+                    if bytes[pos + sublen] == b'-' {
+                        return Err(Error::LabelCannotEndWithHyphen);
+                    }
                 }
-                remaining -= sublen as usize + 1;
+                pos += sublen + 1;
+                remaining -= sublen + 1;
             }
+        }
+    }
+    debug_assert!( pos == bytes.len() - 1 );
+    #[cfg(feature="domain-tld-cannot-be-all-numeric")]
+    if has_numeric_tld(bytes) {
+        return Err(Error::NumericTopLevelDomain)
+    }
+    Ok(())
+}
+
+// Checks if the top level domain is only made of digits
+// (the byte sequence is supposed to be well-structured, the trailing nul byte being optional)
+#[cfg(feature="domain-tld-cannot-be-all-numeric")]
+pub(crate) const fn has_numeric_tld(bytes: &[u8]) -> bool
+{
+    let mut pos = 0;
+    let mut tld = 0;
+    while pos < bytes.len() && bytes[pos] != 0 {
+        tld = pos;
+        pos += bytes[pos] as usize + 1;
+    }
+    if pos == 0 {
+        return false; // the top domain
+    }
+    let mut i = tld + 1;
+    while i <= tld + bytes[tld] as usize {
+        if !bytes[i].is_ascii_digit() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// The maximum length of a label and of the byte sequence of a FQDN (including its
+// trailing nul byte), according to the activated features
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub(crate) const MAX_LABEL_LEN: usize = if cfg!(feature = "domain-label-length-limited-to-63") { 63 } else { 255 };
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+pub(crate) const MAX_NAME_LEN: usize = if cfg!(feature = "domain-name-length-limited-to-255") { 255 } else { u32::MAX as usize };
+
+// Checks if the bytes of a FQDN follow all the rules of the RFC, whatever the activated features
+// (the byte sequence is supposed to be already checked against the activated ones)
+pub(crate) fn check_strict_rfc(bytes: &[u8]) -> Result<(),Error>
+{
+    if bytes.len() > 255 {
+        return Err(Error::TooLongDomainName)
+    }
+    let mut pos = 0;
+    while bytes[pos] != 0 {
+        let label = &bytes[pos + 1..=pos + bytes[pos] as usize];
+        if label.len() > 63 {
+            return Err(Error::TooLongLabel)
+        }
+        if !label.iter().all(|&c| matches!(c, b'a'..=b'z' | b'0'..=b'9' | b'-')) {
+            return Err(Error::InvalidLabelChar)
+        }
+        if label[0] == b'-' {
+            return Err(Error::LabelCannotStartWithHyphen)
+        }
+        if label[label.len() - 1] == b'-' {
+            return Err(Error::LabelCannotEndWithHyphen)
+        }
+        pos += label.len() + 1;
+    }
+    Ok(())
+}
 
-            #[cfg(not(feature="domain-label-cannot-start-or-end-with-hyphen"))]
-            Some(&sublen) => {
-                for _ in 0..sublen {
-                    check_any_char(*iter.next().unwrap())?;
+// Checks if the lengths of a FQDN and of its labels are limited as the RFC specifies,
+// whatever the activated features (as expected by the DNS libraries)
+#[cfg(any(feature = "domain", feature = "hickory"))]
+pub(crate) fn check_rfc_lengths(bytes: &[u8]) -> Result<(),Error>
+{
+    if bytes.len() > 255 {
+        return Err(Error::TooLongDomainName)
+    }
+    let mut pos = 0;
+    while bytes[pos] != 0 {
+        if bytes[pos] > 63 {
+            return Err(Error::TooLongLabel)
+        }
+        pos += bytes[pos] as usize + 1;
+    }
+    Ok(())
+}
+
+// Computes the length of the byte sequence of a FQDN string whose labels are
+// all followed by a dot, as built by the `fqdn!` macro
+// (a redundant trailing dot could be present, as in `github.com..`)
+pub(crate) const fn dotted_str_len(s: &[u8]) -> usize
+{
+    match s.len() {
+        l if l >= 2 && s[l - 2] == b'.' => l - 1,
+        l => l
+    }
+}
+
+// Computes the length of the byte sequence of a FQDN string built by the `fqdn!` macro
+pub const fn dotted_str_wire_len(s: &str) -> usize
+{
+    #[cfg(feature = "punycode")]
+    if !s.is_ascii() {
+        // the punycode encoding is done at runtime, so just reserve the top domain
+        return 1;
+    }
+    match dotted_str_len(s.as_bytes()) {
+        0 | 1 => 1,
+        l => l + 1
+    }
+}
+
+// Converts a FQDN string built by the `fqdn!` macro to its byte sequence
+//
+// This function is const in order to be able to check FQDN at compile time
+pub const fn encode_dotted_str<const N: usize>(s: &str) -> Result<[u8; N], Error>
+{
+    let mut bytes = [0; N];
+    if N == 1 {
+        // the top domain (or a unicode FQDN to encode at runtime)
+        return Ok(bytes);
+    }
+
+    let s = s.as_bytes();
+    let len = dotted_str_len(s);
+    let mut start = 0;
+    while start < len {
+        let mut end = start;
+        while s[end] != b'.' {
+            end += 1;
+        }
+        match end - start {
+            0 => return Err(Error::EmptyLabel),
+            l if l > 255 => return Err(Error::TooLongLabel),
+            l => {
+                bytes[start] = l as u8;
+                let mut i = start;
+                while i < end {
+                    match check_and_lower_any_char(s[i]) {
+                        Ok(c) => bytes[i + 1] = c,
+                        Err(e) => return Err(e),
+                    }
+                    i += 1;
                 }
-                remaining -= sublen as usize + 1;
             }
         }
+        start = end + 1;
+    }
+    match check_byte_sequence(&bytes) {
+        Ok(()) => Ok(bytes),
+        Err(e) => Err(e)
     }
-    debug_assert_eq!( iter.next(), Some(&0));
-    debug_assert!( iter.next().is_none() );
-    Ok(())
 }
 
 
-fn check_any_char(c: u8) -> Result<u8,Error>
+const fn check_any_char(c: u8) -> Result<u8,Error>
 {
     match c {
         b'a'..=b'z' | b'-' | b'0'..=b'9' => Ok(c),
@@ -160,7 +328,7 @@ fn check_any_char(c: u8) -> Result<u8,Error>
     }
 }
 
-pub(crate) fn check_and_lower_any_char(c: u8) -> Result<u8,Error>
+pub(crate) const fn check_and_lower_any_char(c: u8) -> Result<u8,Error>
 {
     /// If the 6th bit is set, ascii is lower case.
     const ASCII_CASE_MASK: u8 = 0b0010_0000;
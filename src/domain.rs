@@ -0,0 +1,219 @@
+use alloc::vec::Vec;
+use core::iter;
+
+use ::domain::base::name::{Label, Name, ToLabelIter, ToName};
+
+use crate::check::{check_byte_sequence, check_rfc_lengths};
+use crate::{Error, Fqdn, FQDN, Labels};
+
+impl<'a> TryFrom<&'a Fqdn> for Name<&'a [u8]> {
+    type Error = Error;
+
+    /// Views a FQDN as a `domain` name, without any copy.
+    ///
+    /// Since `domain` names are limited as the RFC specifies, the conversion fails if a label
+    /// is longer than 63 chars ([`Error::TooLongLabel`]) or if the FQDN is longer than 255 chars
+    /// ([`Error::TooLongDomainName`]), which could not happen since the feature `domain` activates
+    /// `domain-label-length-limited-to-63` and `domain-name-length-limited-to-255`.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use domain::base::Name;
+    ///
+    /// let fqdn = fqdn!("rust-lang.github.io");
+    /// let name = Name::try_from(fqdn.as_ref())?;
+    /// assert_eq![ name.as_slice(), fqdn.as_bytes() ];
+    /// assert_eq![ name.to_string(), "rust-lang.github.io" ];
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[inline]
+    fn try_from(fqdn: &'a Fqdn) -> Result<Self, Self::Error>
+    {
+        check_rfc_lengths(fqdn.as_bytes())?;
+        // it is safe since the lengths of the labels and of the FQDN were just checked
+        Ok(unsafe { Name::from_octets_unchecked(fqdn.as_bytes()) })
+    }
+}
+
+impl<'a> TryFrom<&'a FQDN> for Name<&'a [u8]> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(fqdn: &'a FQDN) -> Result<Self, Self::Error> { fqdn.as_ref().try_into() }
+}
+
+impl TryFrom<FQDN> for Name<Vec<u8>> {
+    type Error = Error;
+
+    /// Moves the byte sequence of a FQDN into a `domain` name, without any copy.
+    #[inline]
+    fn try_from(fqdn: FQDN) -> Result<Self, Self::Error>
+    {
+        check_rfc_lengths(fqdn.as_bytes())?;
+        // it is safe since the lengths of the labels and of the FQDN were just checked
+        Ok(unsafe { Name::from_octets_unchecked(fqdn.into_bytes()) })
+    }
+}
+
+impl<'a, Octs: AsRef<[u8]> + ?Sized> TryFrom<&'a Name<Octs>> for &'a Fqdn {
+    type Error = Error;
+
+    /// Views a `domain` name as a FQDN, without any copy.
+    ///
+    /// Since a FQDN is always lowercase, it fails if the name contains uppercase letters:
+    /// convert it to an owned [`FQDN`] instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use domain::base::Name;
+    ///
+    /// let name = Name::from_slice(b"\x06github\x03com\x00").unwrap();
+    /// assert_eq![ <&Fqdn>::try_from(name), Ok(fqdn!("github.com").as_ref()) ];
+    ///
+    /// let name = Name::from_slice(b"\x06GitHub\x03com\x00").unwrap();
+    /// assert_eq![ <&Fqdn>::try_from(name), Err(Error::InvalidLabelChar) ];
+    /// ```
+    #[inline]
+    fn try_from(name: &'a Name<Octs>) -> Result<Self, Self::Error> { Fqdn::from_bytes(name.as_slice()) }
+}
+
+impl<Octs: AsRef<[u8]> + ?Sized> TryFrom<&Name<Octs>> for FQDN {
+    type Error = Error;
+
+    /// Builds a FQDN from a `domain` name, converting it to lowercase.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use domain::base::Name;
+    ///
+    /// let name = Name::from_slice(b"\x06GitHub\x03com\x00").unwrap();
+    /// assert_eq![ FQDN::try_from(name), Ok(fqdn!("github.com")) ];
+    /// ```
+    fn try_from(name: &Name<Octs>) -> Result<Self, Self::Error>
+    {
+        let bytes = name.as_slice().to_ascii_lowercase();
+        check_byte_sequence(&bytes)?;
+        // it is safe since the byte sequence was just checked
+        Ok(unsafe { FQDN::from_vec_with_nul_unchecked(bytes) })
+    }
+}
+
+impl TryFrom<Name<Vec<u8>>> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(name: Name<Vec<u8>>) -> Result<Self, Self::Error> { FQDN::try_from(&name) }
+}
+
+/// The iterator over the labels of a FQDN, as expected by the `domain` crate.
+///
+/// Notice that, as for `domain` names, the last label is the root one.
+pub type DomainLabels<'a> = iter::Chain<iter::Map<Labels<'a>, fn(&'a str) -> &'a Label>, iter::Once<&'a Label>>;
+
+// a FQDN could be used as a `domain` name since the feature `domain` limits its lengths as the RFC specifies
+impl ToLabelIter for Fqdn {
+    type LabelIter<'a> = DomainLabels<'a>;
+
+    #[inline]
+    fn iter_labels(&self) -> Self::LabelIter<'_>
+    {
+        let label: fn(&str) -> &Label = |label| Label::from_slice(label.as_bytes()).expect("labels are limited to 63 chars");
+        self.labels().map(label).chain(iter::once(Label::root()))
+    }
+
+    #[inline]
+    fn compose_len(&self) -> u16 { self.as_bytes().len() as u16 }
+}
+
+impl ToName for Fqdn {
+    #[inline]
+    fn as_flat_slice(&self) -> Option<&[u8]> { Some(self.as_bytes()) }
+}
+
+impl ToLabelIter for FQDN {
+    type LabelIter<'a> = DomainLabels<'a>;
+
+    #[inline]
+    fn iter_labels(&self) -> Self::LabelIter<'_> { self.as_ref().iter_labels() }
+
+    #[inline]
+    fn compose_len(&self) -> u16 { self.as_ref().compose_len() }
+}
+
+impl ToName for FQDN {
+    #[inline]
+    fn as_flat_slice(&self) -> Option<&[u8]> { Some(self.as_bytes()) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use ::domain::base::Name;
+    use crate as fqdn;
+    use fqdn::*;
+
+    const NAMES: [&str; 5] = [".", "com.", "github.com.", "WWW.Rust-Lang.GitHub.io.", "_sip._tcp.example.org."];
+
+    #[test]
+    fn conversions()
+    {
+        for s in NAMES {
+            let name = Name::<Vec<u8>>::from_str(s).unwrap();
+            let fqdn = s.parse::<FQDN>();
+            assert_eq!(FQDN::try_from(&name), fqdn, "{s}");
+
+            if let Ok(fqdn) = fqdn {
+                assert_eq!(Name::try_from(&fqdn).unwrap(), name, "{s}");
+                assert_eq!(Name::try_from(fqdn.as_ref()).unwrap().as_slice(), fqdn.as_bytes());
+                assert_eq!(<&Fqdn>::try_from(&Name::try_from(&fqdn).unwrap()), Ok(fqdn.as_ref()));
+                assert_eq!(FQDN::try_from(Name::try_from(fqdn.clone()).unwrap()).as_ref(), Ok(&fqdn));
+            }
+        }
+
+        // domain accepts any byte in a label
+        let name = Name::from_slice(b"\x07git@hub\x03com\x00").unwrap();
+        assert_eq!(FQDN::try_from(name), Err(Error::InvalidLabelChar));
+        #[cfg(feature = "domain-label-cannot-start-or-end-with-hyphen")]
+        assert_eq!(FQDN::try_from(Name::from_slice(b"\x07-github\x03com\x00").unwrap()), Err(Error::LabelCannotStartWithHyphen));
+    }
+
+        #[test]
+    fn labels()
+    {
+        use ::domain::base::name::{ToLabelIter, ToName};
+
+        let fqdn = fqdn!("www.rust-lang.github.io");
+        let name = Name::try_from(&fqdn).unwrap();
+        assert!(fqdn.iter_labels().eq(name.iter()));
+        assert!(fqdn.iter_labels().rev().eq(name.iter().rev()));
+        assert!(FQDN::default().iter_labels().eq(Name::root_ref().iter()));
+        assert_eq!(fqdn.compose_len(), name.compose_len());
+        assert_eq!(fqdn.to_name::<Vec<u8>>(), name);
+        assert!(name.ends_with(&fqdn!("github.io")));
+        assert_eq!(name, fqdn);
+        assert_eq!(name, *fqdn);
+    }
+
+        #[test]
+    fn message()
+    {
+        use ::domain::base::iana::Rtype;
+        use ::domain::base::name::ToName;
+        use ::domain::base::{Message, MessageBuilder};
+
+        let fqdn = fqdn!("GitHub.com");
+        let mut msg = MessageBuilder::new_vec().question();
+        msg.push((&fqdn, Rtype::A)).unwrap();
+        msg.push((fqdn.as_ref(), Rtype::AAAA)).unwrap();
+
+        let msg = Message::from_octets(msg.finish()).unwrap();
+        let names = msg.question()
+            .map(|q| FQDN::try_from(&q.unwrap().into_qname().to_name::<Vec<u8>>()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, [fqdn.clone(), fqdn]);
+    }
+}
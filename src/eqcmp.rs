@@ -1,14 +1,19 @@
-use std::cmp::Ordering;
-use crate::{Fqdn, FQDN};
+#[cfg(feature = "alloc")]
+use core::cmp::Ordering;
+use crate::Fqdn;
+#[cfg(feature = "alloc")]
+use crate::FQDN;
 use crate::check::check_and_lower_any_char;
 //--------------------------------------------------------------------------------------
 
+#[cfg(feature = "alloc")]
 impl PartialOrd<FQDN> for Fqdn
 {
     #[inline]
     fn partial_cmp(&self, other: &FQDN) -> Option<Ordering> { self.partial_cmp(other.as_ref()) }
 }
 
+#[cfg(feature = "alloc")]
 impl PartialOrd<Fqdn> for FQDN
 {
     #[inline]
@@ -27,6 +32,7 @@ impl PartialOrd<Fqdn> for FQDN
     fn ge(&self, other: &Fqdn) -> bool { self.as_ref().ge(other) }
 }
 
+#[cfg(feature = "alloc")]
 impl PartialEq<Fqdn> for FQDN
 {
     #[inline]
@@ -34,6 +40,7 @@ impl PartialEq<Fqdn> for FQDN
 }
 
 
+#[cfg(feature = "alloc")]
 impl<S:AsRef<str>> PartialEq<S> for FQDN
 {
     #[inline]
@@ -41,6 +48,7 @@ impl<S:AsRef<str>> PartialEq<S> for FQDN
 }
 
 
+#[cfg(feature = "alloc")]
 impl PartialEq<FQDN> for Fqdn
 {
     #[inline]
@@ -73,6 +81,7 @@ impl<S:AsRef<str>> PartialEq<S> for Fqdn
     }
 }
 
+#[cfg(feature = "alloc")]
 impl PartialEq<FQDN> for &str
 {
     #[inline]
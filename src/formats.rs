@@ -0,0 +1,498 @@
+//! Import and export of the blocklist and resolver formats.
+//!
+//! Lists of domains are read line by line with [`read`], so that very large lists are streamed:
+//! each name is returned with its line number and, if it is invalid, with the reason why
+//! (an invalid name never stops the reading). The lines which do not hold any domain
+//! (comments, other directives, exception rules...) are silently skipped.
+//!
+//! Sets of names are written back with [`write()`].
+//!
+//! # Example
+//! ```
+//! use fqdn::{fqdn, Error};
+//! use fqdn::formats::{self, Entry, EntryError, Format};
+//!
+//! let hosts = "# blocklist\n0.0.0.0 ads.example.com tracker.example.com\n0.0.0.0 ads.exa*mple.com\n";
+//! let entries = formats::read(hosts.as_bytes(), Format::Hosts).collect::<Result<Vec<_>, _>>().unwrap();
+//! assert_eq![ entries, [
+//!     Entry { line: 2, fqdn: Ok(fqdn!("ads.example.com")) },
+//!     Entry { line: 2, fqdn: Ok(fqdn!("tracker.example.com")) },
+//!     Entry { line: 3, fqdn: Err(EntryError::InvalidFqdn(Error::InvalidLabelChar)) },
+//! ]];
+//!
+//! let mut adblock = Vec::new();
+//! let names = entries.iter().filter_map(|e| e.fqdn.as_deref().ok());
+//! formats::write(&mut adblock, Format::AdBlock, names).unwrap();
+//! assert_eq![ adblock, b"||ads.example.com^\n||tracker.example.com^\n" ];
+//! ```
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::net::IpAddr;
+use std::string::String;
+use std::vec::Vec;
+
+use crate::host::parse_host;
+use crate::{Error, Fqdn, FQDN};
+
+/// The supported formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Hosts file, with an IP address followed by names on each line (e.g. `0.0.0.0 ads.example`).
+    ///
+    /// Names are written with the `0.0.0.0` address.
+    Hosts,
+    /// Dnsmasq configuration, with the `address` and `local` directives (e.g. `address=/ads.example/`).
+    ///
+    /// The `server` directives are forwards, so they are skipped unless they have no upstream
+    /// server (e.g. `server=/ads.example/` or `server=/ads.example/#`).
+    ///
+    /// Names are written with `address=/name/`, so that dnsmasq answers `NXDOMAIN`.
+    Dnsmasq,
+    /// Unbound configuration, with the `local-zone` and `local-data` directives
+    /// (e.g. `local-zone: "ads.example." always_nxdomain`).
+    ///
+    /// Only the local zones which block their names are read (`always_nxdomain`, `always_refuse`,
+    /// `always_null`, `refuse`, `deny`, `static` and `redirect`): the other types, as `transparent`,
+    /// let the queries through.
+    ///
+    /// Names are written as `always_nxdomain` local zones.
+    Unbound,
+    /// AdBlock rules which block a whole domain (e.g. `||ads.example^` with optional `$` options).
+    ///
+    /// Exception rules (`@@`), cosmetic rules and rules with a path are skipped.
+    AdBlock,
+    /// Response Policy Zone (e.g. `ads.example CNAME .`).
+    ///
+    /// The owner names are relative to the origin of the zone (set by `$ORIGIN` or by the
+    /// first `SOA` record), which is removed from the absolute ones. A wildcard owner
+    /// (e.g. `*.ads.example`) is read as its parent domain. An absolute owner name outside
+    /// of the zone is reported with [`EntryError::OutOfZone`].
+    ///
+    /// Only the rules which block their name are read: `NXDOMAIN` (`CNAME .`), `NODATA` (`CNAME *.`)
+    /// and local data (any other record). The `rpz-passthru.`, `rpz-drop.` and `rpz-tcp-only.`
+    /// actions are skipped, as are the triggers which are not about the queried name
+    /// (`rpz-ip`, `rpz-nsdname`, `rpz-nsip` and `rpz-client-ip`).
+    ///
+    /// Names are written as relative `NXDOMAIN` rules, for the domain and its subdomains,
+    /// after a minimal `SOA` and `NS` header.
+    Rpz,
+}
+
+/// A name read from a line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// The name or the reason why it is invalid.
+    pub fqdn: Result<FQDN, EntryError>,
+}
+
+/// The reason why a line of a list does not give a valid name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntryError {
+    /// The name is not a valid FQDN (an IP address given instead of a name is reported
+    /// with [`Error::IpAddressLiteral`]).
+    InvalidFqdn(Error),
+    /// The first token of a hosts line is not an IP address.
+    InvalidAddress,
+    /// The absolute owner name of a RPZ rule is outside of the zone.
+    OutOfZone,
+}
+
+impl From<Error> for EntryError {
+    #[inline]
+    fn from(e: Error) -> Self { EntryError::InvalidFqdn(e) }
+}
+
+impl fmt::Display for EntryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            EntryError::InvalidFqdn(e) => fmt::Display::fmt(e, f),
+            EntryError::InvalidAddress => f.write_str("invalid IP address in hosts file"),
+            EntryError::OutOfZone => f.write_str("owner name outside of the RPZ zone"),
+        }
+    }
+}
+
+impl std::error::Error for EntryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)>
+    {
+        match self {
+            EntryError::InvalidFqdn(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the names of a list in the specified format.
+///
+/// The names are case-insensitive and their trailing dot is optional, whatever the activated features.
+/// IP addresses given instead of names are reported with [`Error::IpAddressLiteral`]
+/// (see [`EntryError`] for the other reasons).
+///
+/// The returned iterator only fails on I/O errors (including invalid UTF-8).
+pub fn read<R: BufRead>(reader: R, format: Format) -> Entries<R>
+{
+    Entries { lines: reader.lines(), format, line: 0, pending: VecDeque::new(), rpz: RpzState::default() }
+}
+
+/// The iterator over the names read from a list (see [`read`]).
+#[derive(Debug)]
+pub struct Entries<R> {
+    lines: io::Lines<R>,
+    format: Format,
+    line: usize,
+    pending: VecDeque<Entry>,
+    rpz: RpzState,
+}
+
+// The context of the zone file, which spans several lines
+#[derive(Debug, Default)]
+struct RpzState {
+    origin: Option<FQDN>,
+    parentheses: bool,
+}
+
+impl<R: BufRead> Iterator for Entries<R> {
+    type Item = io::Result<Entry>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        while self.pending.is_empty() {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            self.line += 1;
+            let line_number = self.line;
+            let mut push = |fqdn| self.pending.push_back(Entry { line: line_number, fqdn });
+            match self.format {
+                Format::Hosts => parse_hosts(&line, &mut push),
+                Format::Dnsmasq => parse_dnsmasq(&line, &mut push),
+                Format::Unbound => parse_unbound(&line, &mut push),
+                Format::AdBlock => parse_adblock(&line, &mut push),
+                Format::Rpz => parse_rpz(&line, &mut self.rpz, &mut push),
+            }
+        }
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+// Removes the comment of a line, if any
+fn strip_comment(line: &str, marker: char) -> &str
+{
+    line.split_once(marker).map_or(line, |(line, _)| line)
+}
+
+fn parse_hosts(line: &str, push: &mut impl FnMut(Result<FQDN, EntryError>))
+{
+    let mut tokens = strip_comment(line, '#').split_whitespace();
+    match tokens.next() {
+        None => { /* empty line */ }
+        Some(ip) if ip.parse::<IpAddr>().is_err() => push(Err(EntryError::InvalidAddress)),
+        Some(_) => tokens.for_each(|name| push(parse_name(name))),
+    }
+}
+
+fn parse_dnsmasq(line: &str, push: &mut impl FnMut(Result<FQDN, EntryError>))
+{
+    // a `#` is not a comment inside a directive (e.g. `server=/lan.example/#`),
+    // and the comment lines do not start with any directive
+    let line = line.trim();
+    let domains = ["address=/", "local=/", "server=/"].iter()
+        .find_map(|directive| line.strip_prefix(directive).map(|rest| (directive, rest)))
+        .and_then(|(directive, rest)| rest.rsplit_once('/').map(|(domains, upstream)| (directive, domains, upstream)));
+    match domains {
+        // a forward to an upstream server does not block anything
+        Some((&"server=/", _, upstream)) if !upstream.is_empty() && upstream != "#" => { }
+        Some((_, domains, _)) => domains.split('/').for_each(|name| push(parse_name(name))),
+        None => { }
+    }
+}
+
+// The types of the Unbound local zones which block their names
+const UNBOUND_BLOCKING_ZONES: [&str; 7] = ["always_nxdomain", "always_refuse", "always_null", "refuse", "deny", "static", "redirect"];
+
+fn parse_unbound(line: &str, push: &mut impl FnMut(Result<FQDN, EntryError>))
+{
+    let line = strip_comment(line, '#').trim();
+    if let Some(zone) = line.strip_prefix("local-zone:") {
+        let mut fields = zone.split_whitespace();
+        if let (Some(name), Some(kind)) = (fields.next(), fields.next()) {
+            if UNBOUND_BLOCKING_ZONES.contains(&kind.trim_matches('"')) {
+                push(parse_name(name.trim_matches('"')));
+            }
+        }
+    } else if let Some(data) = line.strip_prefix("local-data:") {
+        let data = data.trim().trim_matches(|c| c == '"' || c == '\'');
+        if let Some(name) = data.split_whitespace().next() {
+            push(parse_name(name));
+        }
+    }
+}
+
+fn parse_adblock(line: &str, push: &mut impl FnMut(Result<FQDN, EntryError>))
+{
+    let rule = line.trim();
+    let rule = rule.split_once('$').map_or(rule, |(rule, _)| rule);
+    if let Some(name) = rule.strip_prefix("||").and_then(|rule| rule.strip_suffix('^')) {
+        // rules with a path are not about the whole domain
+        if !name.contains(['/', '^', '|']) {
+            push(parse_name(name));
+        }
+    }
+}
+
+fn parse_rpz(line: &str, state: &mut RpzState, push: &mut impl FnMut(Result<FQDN, EntryError>))
+{
+    let line = strip_comment(line, ';');
+    if state.parentheses {
+        // continuation of a multiline record (the SOA, most of the time)
+        state.parentheses = !line.contains(')');
+        return;
+    }
+    state.parentheses = line.contains('(') && !line.contains(')');
+
+    // a line starting with a blank is another record of the previous owner
+    if line.starts_with([' ', '\t']) {
+        return;
+    }
+    let mut tokens = line.split_whitespace();
+    let (owner, rest) = match tokens.next() {
+        None => return,
+        Some("$ORIGIN") => {
+            state.origin = tokens.next().and_then(|origin| parse_host(origin).ok());
+            return;
+        }
+        Some(directive) if directive.starts_with('$') => return,
+        Some(owner) => (owner, tokens),
+    };
+
+    if owner == "@" {
+        return; // the apex of the zone
+    }
+    let absolute = owner.ends_with('.') && owner != ".";
+    let fqdn = parse_host(owner.strip_prefix("*.").unwrap_or(owner));
+    let fqdn = match (fqdn, &state.origin) {
+        (Ok(fqdn), None) if absolute && rest.clone().any(|t| t.eq_ignore_ascii_case("SOA")) => {
+            state.origin = Some(fqdn);
+            return;
+        }
+        (Ok(fqdn), Some(origin)) if absolute && fqdn == *origin => return, // the apex of the zone
+        _ if !is_blocking_rule(rest) => return,
+        (Ok(fqdn), Some(origin)) if absolute => {
+            // remove the origin, which is a suffix of the byte sequence
+            match fqdn.is_subdomain_of(origin) {
+                true => FQDN::from_vec(fqdn.as_bytes()[..fqdn.as_bytes().len() - origin.as_bytes().len()].to_vec()),
+                false => return push(Err(EntryError::OutOfZone)),
+            }
+        }
+        (fqdn, _) => fqdn,
+    };
+    // the triggers on IP addresses and name servers are not about the queried names
+    let trigger = fqdn.as_ref().ok().and_then(|fqdn| fqdn.labels().last());
+    if !trigger.is_some_and(|label| RPZ_TRIGGERS.contains(&label)) {
+        push(fqdn.map_err(EntryError::from));
+    }
+}
+
+// The suffixes of the RPZ triggers which are not about the queried names
+const RPZ_TRIGGERS: [&str; 4] = ["rpz-ip", "rpz-nsdname", "rpz-nsip", "rpz-client-ip"];
+
+// Checks if a RPZ rule blocks its owner, given the fields following the owner name:
+// `NXDOMAIN` (`CNAME .`), `NODATA` (`CNAME *.`) and local data do, but not the special
+// actions (`CNAME rpz-passthru.`, `CNAME rpz-drop.`...) nor the records of the zone itself
+fn is_blocking_rule<'a>(mut fields: impl Iterator<Item = &'a str>) -> bool
+{
+    // skip the optional TTL and class
+    let rtype = fields.find(|field| {
+        !field.starts_with(|c: char| c.is_ascii_digit())
+            && !["IN", "CH", "HS", "CS"].iter().any(|class| field.eq_ignore_ascii_case(class))
+    });
+    match rtype {
+        None => false,
+        Some(rtype) if rtype.eq_ignore_ascii_case("CNAME") => {
+            fields.next().is_some_and(|target| !target.get(..4).is_some_and(|prefix| prefix.eq_ignore_ascii_case("rpz-")))
+        }
+        Some(rtype) => !rtype.eq_ignore_ascii_case("SOA") && !rtype.eq_ignore_ascii_case("NS"),
+    }
+}
+
+// Parses a name of a list
+#[inline]
+fn parse_name(name: &str) -> Result<FQDN, EntryError> { parse_host(name).map_err(EntryError::from) }
+
+/// Writes a set of names in the specified format.
+///
+/// The names are written in the given order, one line per name (two lines for [`Format::Rpz`]).
+/// The top domain is skipped since it cannot be expressed in most formats.
+pub fn write<'a, W: Write>(mut writer: W, format: Format, names: impl IntoIterator<Item = &'a Fqdn>) -> io::Result<()>
+{
+    if format == Format::Rpz {
+        writeln!(writer, "$TTL 300")?;
+        writeln!(writer, "@ SOA localhost. root.localhost. 1 3600 600 86400 300")?;
+        writeln!(writer, "  NS localhost.")?;
+    }
+    for fqdn in names.into_iter().filter(|fqdn| !fqdn.is_root()) {
+        let name = dotless(fqdn);
+        match format {
+            Format::Hosts => writeln!(writer, "0.0.0.0 {name}")?,
+            Format::Dnsmasq => writeln!(writer, "address=/{name}/")?,
+            Format::Unbound => writeln!(writer, "local-zone: \"{name}.\" always_nxdomain")?,
+            Format::AdBlock => writeln!(writer, "||{name}^")?,
+            Format::Rpz => writeln!(writer, "{name} CNAME .\n*.{name} CNAME .")?,
+        }
+    }
+    writer.flush()
+}
+
+// Formats a name without its trailing dot, whatever the activated features
+fn dotless(fqdn: &Fqdn) -> String
+{
+    fqdn.labels().collect::<Vec<_>>().join(".")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::formats::*;
+    use fqdn::*;
+
+    // reads all the valid and invalid names of a list
+    fn read_all(list: &str, format: Format) -> Vec<(usize, Result<FQDN, EntryError>)>
+    {
+        read(list.as_bytes(), format)
+            .map(|entry| entry.map(|e| (e.line, e.fqdn)))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn hosts()
+    {
+        let list = "# comment\n\n127.0.0.1 localhost\n0.0.0.0 Ads.Example.com. tracker.example.com # inline\n::1 ip6-localhost\n\
+                    ads.example.com\n0.0.0.0 0.0.0.0\n";
+        assert_eq!(read_all(list, Format::Hosts), [
+            (3, Ok(fqdn!("localhost"))),
+            (4, Ok(fqdn!("ads.example.com"))),
+            (4, Ok(fqdn!("tracker.example.com"))),
+            (5, Ok(fqdn!("ip6-localhost"))),
+            (6, Err(EntryError::InvalidAddress)),
+            (7, Err(EntryError::InvalidFqdn(Error::IpAddressLiteral))),
+        ]);
+    }
+
+    #[test]
+    fn dnsmasq()
+    {
+        let list = "# comment\naddress=/ads.example.com/0.0.0.0\nlocal=/a.example/b.example/\nserver=/corp.example/10.0.0.1\n\
+                    cache-size=1000\naddress=/git@hub.com/\nserver=/lan.example/\nserver=/dns.example/#\n";
+        assert_eq!(read_all(list, Format::Dnsmasq), [
+            (2, Ok(fqdn!("ads.example.com"))),
+            (3, Ok(fqdn!("a.example"))),
+            (3, Ok(fqdn!("b.example"))),
+            (6, Err(EntryError::InvalidFqdn(Error::InvalidLabelChar))),
+            (7, Ok(fqdn!("lan.example"))),
+            (8, Ok(fqdn!("dns.example"))),
+        ]);
+    }
+
+    #[test]
+    fn unbound()
+    {
+        let list = "server:\n  # comment\n  local-zone: \"ads.example.com.\" always_nxdomain\n  local-zone: tracker.example static\n\
+                    \x20 local-data: \"host.example.com. A 0.0.0.0\"\n  verbosity: 1\n\
+                    \x20 local-zone: \"good.example.\" transparent\n  local-zone: lan.example typetransparent\n\
+                    \x20 local-zone: \"ok.example.\" always_transparent\n  local-zone: \"refused.example.\" always_refuse\n\
+                    \x20 local-zone: \"notype.example.\"\n";
+        assert_eq!(read_all(list, Format::Unbound), [
+            (3, Ok(fqdn!("ads.example.com"))),
+            (4, Ok(fqdn!("tracker.example"))),
+            (5, Ok(fqdn!("host.example.com"))),
+            (10, Ok(fqdn!("refused.example"))),
+        ]);
+    }
+
+    #[test]
+    fn adblock()
+    {
+        let list = "[Adblock Plus 2.0]\n! comment\n||ads.example.com^\n||tracker.example^$third-party\n@@||good.example^\n\
+                    ||example.org/ads^\nexample.net##.banner\n||ads*.example^\n";
+        assert_eq!(read_all(list, Format::AdBlock), [
+            (3, Ok(fqdn!("ads.example.com"))),
+            (4, Ok(fqdn!("tracker.example"))),
+            (8, Err(EntryError::InvalidFqdn(Error::InvalidLabelChar))),
+        ]);
+    }
+
+    #[test]
+    fn rpz()
+    {
+        let list = "$TTL 300\n$ORIGIN rpz.example.\n@ SOA ns.example. admin.example. (\n  1 3600\n  600 86400 300 )\n  NS ns.example.\n\
+                    ads.example.com CNAME .\n*.ads.example.com CNAME .\ntracker.example.rpz.example. CNAME . ; absolute\n\
+                    other.example. CNAME .\n";
+        assert_eq!(read_all(list, Format::Rpz), [
+            (7, Ok(fqdn!("ads.example.com"))),
+            (8, Ok(fqdn!("ads.example.com"))),
+            (9, Ok(fqdn!("tracker.example"))),
+            (10, Err(EntryError::OutOfZone)),
+        ]);
+
+        // the origin given by the SOA record
+        let list = "rpz.example. 300 IN SOA ns.example. admin.example. 1 3600 600 86400 300\nads.example.rpz.example. CNAME .\n";
+        assert_eq!(read_all(list, Format::Rpz), [(2, Ok(fqdn!("ads.example")))]);
+    }
+
+    #[test]
+    fn rpz_actions()
+    {
+        let list = "$ORIGIN rpz.example.\n\
+                    nxdomain.example CNAME .\n\
+                    nodata.example 300 IN CNAME *.\n\
+                    local.example A 192.0.2.1\n\
+                    garden.example CNAME walled-garden.example.\n\
+                    passthru.example CNAME rpz-passthru.\n\
+                    drop.example IN CNAME RPZ-DROP.\n\
+                    tcp.example CNAME rpz-tcp-only.\n\
+                    bad..example CNAME rpz-passthru.\n\
+                    ok.other.example. CNAME rpz-passthru.\n\
+                    ns.example.rpz.example. NS ns.example.\n";
+        assert_eq!(read_all(list, Format::Rpz), [
+            (2, Ok(fqdn!("nxdomain.example"))),
+            (3, Ok(fqdn!("nodata.example"))),
+            (4, Ok(fqdn!("local.example"))),
+            (5, Ok(fqdn!("garden.example"))),
+        ]);
+    }
+
+    #[test]
+    fn rpz_triggers()
+    {
+        let list = "$ORIGIN rpz.example.\n\
+                    32.1.2.0.192.rpz-ip CNAME .\n\
+                    ns.bad.example.rpz-nsdname CNAME .\n\
+                    32.53.2.0.192.rpz-nsip.rpz.example. CNAME .\n\
+                    24.0.2.0.192.rpz-client-ip CNAME rpz-drop.\n\
+                    128.zz.db8.2001.rpz-client-ip CNAME .\n\
+                    rpz-ip.example CNAME .\n";
+        assert_eq!(read_all(list, Format::Rpz), [(7, Ok(fqdn!("rpz-ip.example")))]);
+    }
+
+    #[test]
+    fn round_trip()
+    {
+        let names = [fqdn!("ads.example.com"), fqdn!("tracker.example"), FQDN::default()];
+        for format in [Format::Hosts, Format::Dnsmasq, Format::Unbound, Format::AdBlock, Format::Rpz] {
+            let mut list = Vec::new();
+            write(&mut list, format, names.iter().map(|n| n.as_ref())).unwrap();
+            let mut read = read(list.as_slice(), format)
+                .map(|e| e.unwrap().fqdn.unwrap())
+                .collect::<Vec<_>>();
+            read.dedup();
+            assert_eq!(read, names[..2], "{format:?}");
+        }
+    }
+}
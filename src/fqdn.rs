@@ -1,13 +1,20 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::ffi::CString;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::ffi::CStr;
+use core::fmt;
+use core::fmt::Formatter;
+use core::hash::Hash;
 use core::ops;
-use std::ffi::{CStr, CString};
-use std::fmt;
-use std::fmt::Formatter;
+use core::str::FromStr;
 
 use crate::check::*;
 use crate::*;
-use std::borrow::Borrow;
-use std::hash::Hash;
-use std::str::FromStr;
 
 /// A FQDN string.
 ///
@@ -66,6 +73,10 @@ impl FQDN {
                 }
             }
         }
+        #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+        if has_numeric_tld(&bytes) {
+            return Err(Error::NumericTopLevelDomain);
+        }
         Ok(unsafe { Self::from_vec_with_nul_unchecked(bytes) })
     }
 
@@ -78,7 +89,7 @@ impl FQDN {
     /// * the label length is too high, or
     /// * the total length is too high, or
     /// * a not allowed character is used
-    unsafe fn from_vec_with_nul_unchecked(v: Vec<u8>) -> Self {
+    pub(crate) unsafe fn from_vec_with_nul_unchecked(v: Vec<u8>) -> Self {
         FQDN(CString::from_vec_with_nul_unchecked(v))
     }
 
@@ -120,7 +131,7 @@ impl FQDN {
         }
 
         // now, check each FQDN subpart and concatenate them
-        toparse
+        let bytes = toparse
             .split(|&c| c == b'.')
             .try_fold(
                 Vec::with_capacity(s.len() + 1),
@@ -162,11 +173,59 @@ impl FQDN {
                         Ok(bytes)
                     }
                 },
-            )
-            .map(|bytes| Self(unsafe { CString::from_vec_unchecked(bytes) }))
+            )?;
+
+        #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+        if has_numeric_tld(&bytes) {
+            return Err(Error::NumericTopLevelDomain);
+        }
+        Ok(Self(unsafe { CString::from_vec_unchecked(bytes) }))
     }
 }
 
+impl FQDN {
+
+    /// Extracts a [`Fqdn`] slice containing the entire FQDN.
+    #[inline]
+    pub fn as_fqdn(&self) -> &Fqdn { self.as_ref() }
+
+    /// Converts this FQDN into a boxed [`Fqdn`].
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let boxed = fqdn!("crates.io").into_boxed_fqdn();
+    /// assert_eq![ FQDN::from(boxed), fqdn!("crates.io") ];
+    /// ```
+    #[inline]
+    pub fn into_boxed_fqdn(self) -> Box<Fqdn> {
+        let raw = Box::into_raw(self.0.into_boxed_c_str()) as *mut Fqdn;
+        // SAFE because Fqdn is just a transparent wrapper around CStr
+        unsafe { Box::from_raw(raw) }
+    }
+
+    /// Converts this FQDN into its byte sequence, including the trailing nul byte.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// assert_eq![ fqdn!("crates.io").into_bytes(), b"\x06crates\x02io\x00" ];
+    /// ```
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> { self.0.into_bytes_with_nul() }
+
+    /// Converts this FQDN into a C string (with the same byte sequence).
+    #[inline]
+    pub fn into_c_string(self) -> CString { self.0 }
+}
+
+impl Fqdn {
+
+    /// Converts a boxed FQDN into an owned [`FQDN`] without copying nor allocating.
+    #[inline]
+    pub fn into_fqdn(self: Box<Self>) -> FQDN { self.into() }
+}
+
 impl AsRef<Fqdn> for FQDN {
     #[inline]
     fn as_ref(&self) -> &Fqdn {
@@ -190,14 +249,142 @@ impl From<&Fqdn> for FQDN {
     }
 }
 
+impl From<&FQDN> for FQDN {
+    #[inline]
+    fn from(s: &FQDN) -> FQDN { s.clone() }
+}
+
 impl From<Box<Fqdn>> for FQDN {
     #[inline]
     fn from(s: Box<Fqdn>) -> FQDN {
-        let cstr: Box<CStr> = unsafe { std::mem::transmute(s) };
+        // SAFE because Fqdn is just a transparent wrapper around CStr
+        let cstr: Box<CStr> = unsafe { Box::from_raw(Box::into_raw(s) as *mut CStr) };
         FQDN(cstr.into())
     }
 }
 
+impl<'a> From<Cow<'a, Fqdn>> for FQDN {
+    #[inline]
+    fn from(s: Cow<'a, Fqdn>) -> FQDN { s.into_owned() }
+}
+
+impl From<FQDN> for Box<Fqdn> {
+    #[inline]
+    fn from(s: FQDN) -> Box<Fqdn> { s.into_boxed_fqdn() }
+}
+
+impl From<&Fqdn> for Box<Fqdn> {
+    #[inline]
+    fn from(s: &Fqdn) -> Box<Fqdn> { FQDN::from(s).into_boxed_fqdn() }
+}
+
+impl Clone for Box<Fqdn> {
+    #[inline]
+    fn clone(&self) -> Self { self.as_ref().into() }
+}
+
+impl Default for Box<Fqdn> {
+    /// Builds the top domain.
+    #[inline]
+    fn default() -> Self { FQDN::default().into_boxed_fqdn() }
+}
+
+impl From<FQDN> for Arc<Fqdn> {
+    #[inline]
+    fn from(s: FQDN) -> Arc<Fqdn> {
+        let arc: Arc<CStr> = s.0.into();
+        // SAFE because Fqdn is just a transparent wrapper around CStr
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const Fqdn) }
+    }
+}
+
+impl From<&Fqdn> for Arc<Fqdn> {
+    #[inline]
+    fn from(s: &Fqdn) -> Arc<Fqdn> {
+        let arc: Arc<CStr> = s.0.into();
+        // SAFE because Fqdn is just a transparent wrapper around CStr
+        unsafe { Arc::from_raw(Arc::into_raw(arc) as *const Fqdn) }
+    }
+}
+
+impl From<FQDN> for Rc<Fqdn> {
+    #[inline]
+    fn from(s: FQDN) -> Rc<Fqdn> {
+        let rc: Rc<CStr> = s.0.into();
+        // SAFE because Fqdn is just a transparent wrapper around CStr
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const Fqdn) }
+    }
+}
+
+impl From<&Fqdn> for Rc<Fqdn> {
+    #[inline]
+    fn from(s: &Fqdn) -> Rc<Fqdn> {
+        let rc: Rc<CStr> = s.0.into();
+        // SAFE because Fqdn is just a transparent wrapper around CStr
+        unsafe { Rc::from_raw(Rc::into_raw(rc) as *const Fqdn) }
+    }
+}
+
+impl<'a> From<&'a Fqdn> for Cow<'a, Fqdn> {
+    #[inline]
+    fn from(s: &'a Fqdn) -> Cow<'a, Fqdn> { Cow::Borrowed(s) }
+}
+
+impl<'a> From<&'a FQDN> for Cow<'a, Fqdn> {
+    #[inline]
+    fn from(s: &'a FQDN) -> Cow<'a, Fqdn> { Cow::Borrowed(s.as_ref()) }
+}
+
+impl From<FQDN> for Cow<'_, Fqdn> {
+    #[inline]
+    fn from(s: FQDN) -> Self { Cow::Owned(s) }
+}
+
+impl From<FQDN> for CString {
+    #[inline]
+    fn from(s: FQDN) -> CString { s.into_c_string() }
+}
+
+impl From<FQDN> for Vec<u8> {
+    #[inline]
+    fn from(s: FQDN) -> Vec<u8> { s.into_bytes() }
+}
+
+impl From<FQDN> for String {
+    /// Builds the human-readable representation of the FQDN (as [`ToString`] does).
+    #[inline]
+    fn from(s: FQDN) -> String { s.to_string() }
+}
+
+impl From<&Fqdn> for String {
+    /// Builds the human-readable representation of the FQDN (as [`ToString`] does).
+    #[inline]
+    fn from(s: &Fqdn) -> String { s.to_string() }
+}
+
+impl TryFrom<CString> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: CString) -> Result<FQDN, Self::Error> {
+        Self::from_vec(s.into_bytes_with_nul())
+    }
+}
+
+impl TryFrom<&str> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<FQDN, Self::Error> { s.parse() }
+}
+
+impl TryFrom<String> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(s: String) -> Result<FQDN, Self::Error> { s.parse() }
+}
+
 impl TryFrom<Vec<u8>> for FQDN {
     type Error = Error;
 
@@ -236,20 +423,3 @@ impl FromStr for FQDN {
         }
     }
 }
-
-#[cfg(feature = "serde")]
-impl serde::Serialize for FQDN {
-    #[inline]
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        self.to_string().serialize(serializer)
-    }
-}
-
-#[cfg(feature = "serde")]
-impl<'de> serde::Deserialize<'de> for FQDN {
-    #[inline]
-    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        String::deserialize(deserializer)
-            .and_then(|str| Self::from_str(&str).map_err(serde::de::Error::custom))
-    }
-}
@@ -1,9 +1,9 @@
-use std::ffi::CStr;
-use std::fmt;
-use std::fmt::{Formatter, Write};
+use core::ffi::CStr;
+use core::fmt;
+use core::fmt::{Formatter, Write};
 
 use crate::*;
-use std::hash::Hash;
+use core::hash::Hash;
 
 
 /// A borrowed FQDN (as a slice).
@@ -11,16 +11,9 @@ use std::hash::Hash;
 /// [`&Fqdn`](`crate::Fqdn`) is to [`FQDN`](`crate::FQDN`) as [`&str`] is to [`String`]:
 /// the former in each pair are borrowed references; the latter are owned data.
 #[derive(Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[repr(transparent)]
 pub struct Fqdn(pub(crate) CStr);
 
-#[cfg(feature = "serde")]
-impl serde::Serialize for Fqdn {
-    #[inline]
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        self.to_string().serialize(serializer)
-    }
-}
-
 impl Fqdn {
 
     /// Checks if this is the top domain.
@@ -37,8 +30,10 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// assert![ ! fqdn!("github.com.").is_tld() ];
     /// assert![ fqdn!("com").is_tld() ];
+    /// # }
     /// ```
     #[inline]
     pub fn is_tld(&self) -> bool
@@ -53,10 +48,12 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// assert![ fqdn!("github.com.").is_subdomain_of(&fqdn!("github.com.")) ];
     /// assert![ fqdn!("www.rust-lang.github.com").is_subdomain_of(&fqdn!("github.com.")) ];
     ///
     /// assert![ ! fqdn!("github.com.").is_subdomain_of(&fqdn!("www.rust-lang.github.com")) ];
+    /// # }
     /// ```
     #[inline]
     pub fn is_subdomain_of(&self, parent:&Fqdn) -> bool
@@ -79,13 +76,46 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// # use std::str::FromStr;
     /// assert_eq![ fqdn!("rust-lang.github.com.").tld(), Some(fqdn!("com.").as_ref()) ];
     /// assert_eq![ fqdn!(".").tld(), None ];
+    /// # }
     /// ```
     #[inline]
     pub fn tld(&self) -> Option<&Fqdn> { self.hierarchy().last() }
 
+    /// Checks if this FQDN could be mistaken for an IPv4 address.
+    ///
+    /// As the [WHATWG URL Standard](https://url.spec.whatwg.org/#ends-in-a-number-checker) does,
+    /// a FQDN looks like an IP literal if its top level domain is a number, either decimal
+    /// or hexadecimal (prefixed by `0x`). Browsers parse such hosts as IPv4 addresses,
+    /// even the non-dotted forms as `2130706433` or `0x7f.1`.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// # #[cfg(not(feature = "domain-tld-cannot-be-all-numeric"))] {
+    /// assert![ fqdn!("127.0.0.1").looks_like_ip_literal() ];
+    /// assert![ fqdn!("2130706433").looks_like_ip_literal() ];
+    /// # }
+    /// assert![ fqdn!("github.0x7f").looks_like_ip_literal() ];
+    /// assert![ ! fqdn!("4chan.org").looks_like_ip_literal() ];
+    /// assert![ ! fqdn!("github.0xgg").looks_like_ip_literal() ];
+    /// # }
+    /// ```
+    pub fn looks_like_ip_literal(&self) -> bool
+    {
+        match self.labels().next_back() {
+            None => false,
+            Some(tld) => match tld.strip_prefix("0x") {
+                Some(hex) => hex.bytes().all(|b| b.is_ascii_hexdigit()),
+                None => tld.bytes().all(|b| b.is_ascii_digit())
+            }
+        }
+    }
+
     /// Extracts a `Fqdn` slice with contains the immediate parent domain.
     ///
     /// The parent is the domain after remaining the first label.
@@ -96,49 +126,120 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// # use std::str::FromStr;
     /// assert_eq![ fqdn!("github.com").parent(), Some(fqdn!("com").as_ref()) ];
     /// assert_eq![ fqdn!("github.com").parent().unwrap().parent(), None ];
     /// assert_eq![ fqdn!(".").parent(), None ];
+    /// # }
     /// ```
     #[inline]
     pub fn parent(&self) -> Option<&Fqdn> { self.hierarchy().nth(1) }
 
 
     /// Iterates over the parents of the FQDN.
+    ///
+    /// The returned iterator is double-ended: iterating it backward walks
+    /// from the TLD down to this FQDN (see also [`Self::ancestors`]).
+    ///
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// # use std::str::FromStr;
     /// let fqdn = fqdn!("rust-lang.github.com.");
     /// let mut iter = fqdn.hierarchy();
+    /// assert_eq![ iter.len(), 3 ];
     /// assert_eq![ iter.next(), Some(fqdn!("rust-lang.github.com.").as_ref()) ];
     /// assert_eq![ iter.next(), Some(fqdn!("github.com.").as_ref()) ];
     /// assert_eq![ iter.next(), Some(fqdn!("com.").as_ref()) ];
     /// assert_eq![ iter.next(), None ];
+    /// # }
+    /// ```
+    #[inline]
+    pub fn hierarchy(&self) -> Hierarchy<'_> { Hierarchy::new(self) }
+
+    /// Iterates over the ancestors of the FQDN, starting from the TLD.
+    ///
+    /// This is the reverse order of [`Self::hierarchy`]: the last item is the FQDN itself.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let fqdn = fqdn!("rust-lang.github.com.");
+    /// let mut iter = fqdn.ancestors();
+    /// assert_eq![ iter.next(), Some(fqdn!("com.").as_ref()) ];
+    /// assert_eq![ iter.next(), Some(fqdn!("github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), Some(fqdn!("rust-lang.github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), None ];
+    /// # }
     /// ```
     #[inline]
-    pub fn hierarchy(&self) -> impl '_ + Iterator<Item=&Fqdn>
+    pub fn ancestors(&self) -> Ancestors<'_> { Ancestors(self.hierarchy()) }
+
+    /// Iterates over the ancestors of the FQDN which belong to the given zone,
+    /// starting from the zone itself.
+    ///
+    /// If this FQDN is not a subdomain of `zone`, the iterator is empty.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let fqdn = fqdn!("www.rust-lang.github.com.");
+    /// let zone = fqdn!("github.com.");
+    /// let mut iter = fqdn.ancestors_within(&zone);
+    /// assert_eq![ iter.next(), Some(fqdn!("github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), Some(fqdn!("rust-lang.github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), Some(fqdn!("www.rust-lang.github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), None ];
+    ///
+    /// assert_eq![ fqdn.ancestors_within(&fqdn!("crates.io.")).next(), None ];
+    /// # }
+    /// ```
+    pub fn ancestors_within(&self, zone: &Fqdn) -> Ancestors<'_>
     {
-        struct Iter<'a>(&'a Fqdn);
-
-        impl<'a> Iterator for Iter<'a>
-        {
-            type Item = &'a Fqdn;
-            fn next(&mut self) -> Option<<Self as Iterator>::Item>
-            {
-                match self.0.first_label_length() {
-                    0 => None,
-                    len => {
-                        let current = self.0;
-                        // it is safe because of the inner structure of FQDN
-                        self.0 = unsafe { &*(&self.0.0[1 + len..] as *const CStr as *const Fqdn) };
-                        Some(current)
-                    }
-                }
-            }
-        }
-        Iter(self)
+        let bytes = self.as_bytes();
+        let back = if !self.is_subdomain_of(zone) {
+            0
+        } else if zone.is_root() {
+            bytes.len() - 1
+        } else {
+            // the zone is a suffix of this FQDN, so stop just after its first label
+            bytes.len() - zone.as_bytes().len() + zone.first_label_length() + 1
+        };
+        Ancestors(Hierarchy::bounded(bytes, back))
+    }
+
+    /// Iterates over the names to query in order to resolve this FQDN with QNAME minimisation
+    /// ([RFC 9156](https://www.rfc-editor.org/rfc/rfc9156#section-2.3)), starting below a known zone cut.
+    ///
+    /// The first [`MINIMISE_ONE_LAB`](crate::MINIMISE_ONE_LAB) queries add one label at a time, then the
+    /// remaining labels are spread over the remaining queries, so that there are at most
+    /// [`MAX_MINIMISE_COUNT`](crate::MAX_MINIMISE_COUNT) queries. The last one is always this FQDN
+    /// and its leading labels which start with an underscore are added all at once
+    /// (as `_25._tcp.mail.example.org`, which is queried right after `mail.example.org`).
+    /// If `known_zone` is not an ancestor of this FQDN, the minimisation starts from the top domain.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let fqdn = fqdn!("www.rust-lang.github.com.");
+    /// let mut iter = fqdn.qname_minimisation_steps(&fqdn!("com."));
+    /// assert_eq![ iter.next(), Some(fqdn!("github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), Some(fqdn!("rust-lang.github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), Some(fqdn!("www.rust-lang.github.com.").as_ref()) ];
+    /// assert_eq![ iter.next(), None ];
+    ///
+    /// assert_eq![ fqdn.qname_minimisation_steps(&fqdn).collect::<Vec<_>>(), [fqdn.as_ref()] ];
+    /// # }
+    /// ```
+    #[inline]
+    pub fn qname_minimisation_steps(&self, known_zone: &Fqdn) -> QnameMinimisation<'_>
+    {
+        QnameMinimisation::new(self, known_zone)
     }
 
     /// Computes the depth of this domain (i.e. counts the labels)
@@ -146,13 +247,33 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// # use std::str::FromStr;
     /// assert_eq![ fqdn!("rust-lang.github.com.").depth(), 3 ];
     /// assert_eq![ fqdn!("github.com.").depth(), 2 ];
     /// assert_eq![ fqdn!(".").depth(), 0 ];
+    /// # }
+    /// ```
+    #[inline]
+    pub fn depth(&self) -> usize { self.hierarchy().len() }
+
+    /// Checks if this FQDN follows all the rules of the RFC, whatever the activated features.
+    ///
+    /// This is useful to apply the `strict-rfc` rules at runtime, for instance to report
+    /// names which are accepted by this crate but not by stricter DNS software.
+    /// The trailing dot, which only concerns strings, is not checked.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert_eq![ fqdn!("github.com").check_strict_rfc(), Ok(()) ];
+    /// # #[cfg(not(feature = "domain-name-without-special-chars"))]
+    /// assert_eq![ fqdn!("_sip._tcp.example.org").check_strict_rfc(), Err(Error::InvalidLabelChar) ];
+    /// # }
     /// ```
     #[inline]
-    pub fn depth(&self) -> usize { self.hierarchy().count() }
+    pub fn check_strict_rfc(&self) -> Result<(),Error> { check::check_strict_rfc(self.as_bytes()) }
 
     /// Builds a FQDN from a byte sequence.
     ///
@@ -165,12 +286,14 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// # use std::str::FromStr;
     /// assert_eq![ Fqdn::from_bytes(b"\x06crates\x02io\x00"), Ok(fqdn!("crates.io.").as_ref()) ];
     ///
     /// assert_eq![ Fqdn::from_bytes(b"\x06crates\x02io"),     Err(Error::TrailingNulCharMissing) ];
     /// assert_eq![ Fqdn::from_bytes(b"\x06cr@tes\x02io\x00"), Err(Error::InvalidLabelChar) ];
     /// assert_eq![ Fqdn::from_bytes(b"\x02crates\x02io\x00"), Err(Error::InvalidStructure) ];
+    /// # }
     /// ```
     #[inline]
     pub fn from_bytes(bytes: &[u8]) -> Result<&Self,Error>
@@ -183,6 +306,36 @@ impl Fqdn {
             })
     }
 
+    /// Builds a FQDN from a static byte sequence, in a const context.
+    ///
+    /// This allows to declare FQDN as constants or statics, which are checked
+    /// at compile time.
+    ///
+    /// # Panics
+    /// If the byte sequence does not follow the rules (see [`Self::from_bytes`]), this function panics,
+    /// so it fails to compile when used in a const context.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// static ROOT_SERVERS: &Fqdn = Fqdn::from_static_bytes(b"\x0croot-servers\x03net\x00");
+    /// assert_eq![ *ROOT_SERVERS, fqdn!("root-servers.net.") ];
+    /// # }
+    /// ```
+    /// ```compile_fail
+    /// # use fqdn::*;
+    /// const INVALID: &Fqdn = Fqdn::from_static_bytes(b"\x06cr@tes\x02io\x00");
+    /// ```
+    pub const fn from_static_bytes(bytes: &'static [u8]) -> &'static Self
+    {
+        match check::check_byte_sequence(bytes) {
+            // it is safe because check does the necessary stuff... (including trailing nul char)
+            Ok(()) => unsafe { Self::from_bytes_unchecked(bytes) },
+            Err(e) => panic!("{}", e.as_str())
+        }
+    }
+
     /// Builds without any check a FQDN from a byte sequence.
     ///
     /// # Safety
@@ -200,13 +353,15 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// let crates = unsafe {
     ///     Fqdn::from_bytes_unchecked(b"\x06crates\x02io\x00")
     /// };
     /// assert_eq![ *crates, fqdn!("crates.io.") ];
+    /// # }
     /// ```
     #[inline]
-    pub unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self
+    pub const unsafe fn from_bytes_unchecked(bytes: &[u8]) -> &Self
     {
         &*(CStr::from_bytes_with_nul_unchecked(bytes) as *const CStr as *const Fqdn)
     }
@@ -218,7 +373,9 @@ impl Fqdn {
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// assert_eq![ fqdn!("crates.io.").as_bytes(),  b"\x06crates\x02io\x00" ];
+    /// # }
     /// ```
     #[inline]
     pub fn as_bytes(&self) -> &[u8] { self.0.to_bytes_with_nul() }
@@ -229,9 +386,14 @@ impl Fqdn {
 
 
     /// Iterates over the labels which constitutes the FQDN.
+    ///
+    /// The returned iterator is double-ended, so the labels could also be
+    /// iterated from the TLD by using [`Iterator::rev`].
+    ///
     /// # Example
     /// ```
     /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
     /// # use std::str::FromStr;
     /// let fqdn = fqdn!("rust-lang.github.com.");
     /// let mut iter = fqdn.labels();
@@ -240,30 +402,79 @@ impl Fqdn {
     /// assert_eq![ iter.next(), Some("com") ];
     /// assert_eq![ iter.next(), None ];
     /// # assert_eq![ iter.next(), None ];
+    ///
+    /// assert![ fqdn.labels().rev().eq(["com", "github", "rust-lang"]) ];
+    /// # }
+    /// ```
+    #[inline]
+    pub fn labels(&self) -> Labels<'_> { Labels(self.hierarchy()) }
+
+    /// Gets the label at the given position, starting from the leftmost one.
+    ///
+    /// If the position is out of range, `None` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let fqdn = fqdn!("rust-lang.github.com.");
+    /// assert_eq![ fqdn.label(0), Some("rust-lang") ];
+    /// assert_eq![ fqdn.label(2), Some("com") ];
+    /// assert_eq![ fqdn.label(3), None ];
+    /// # }
     /// ```
     #[inline]
-    pub fn labels(&self) -> impl '_ + Iterator<Item=&str>
+    pub fn label(&self, i: usize) -> Option<&str> { self.labels().nth(i) }
+
+    /// Extracts the suffix of this FQDN made of its last `n` labels.
+    ///
+    /// The suffix of length 0 is the top domain and the suffix of length [`Self::depth`]
+    /// is the FQDN itself. If `n` is greater than the depth, `None` is returned.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let fqdn = fqdn!("rust-lang.github.com.");
+    /// assert_eq![ fqdn.suffix(0), Some(fqdn!(".").as_ref()) ];
+    /// assert_eq![ fqdn.suffix(2), Some(fqdn!("github.com.").as_ref()) ];
+    /// assert_eq![ fqdn.suffix(3), Some(fqdn.as_ref()) ];
+    /// assert_eq![ fqdn.suffix(4), None ];
+    /// # }
+    /// ```
+    pub fn suffix(&self, n: usize) -> Option<&Fqdn>
     {
-        self.hierarchy().map(move |s|
-            // it is safe because a FQDN contains only ASCII characters
-            unsafe { std::str::from_utf8_unchecked(&s.as_bytes()[1..=s.first_label_length()]) }
-        )
+        match n {
+            // it is safe since the trailing nul byte is the top domain
+            0 => Some(unsafe { Self::from_bytes_unchecked(&self.as_bytes()[self.as_bytes().len() - 1..]) }),
+            n => self.ancestors().nth(n - 1)
+        }
     }
 
     // for internal use
     #[inline]
-    fn first_label_length(&self) -> usize {
+    pub(crate) fn first_label_length(&self) -> usize {
         // this is safe because of the inner structure of FQDN...
         unsafe { *self.as_bytes().get_unchecked(0) as usize }
     }
 }
 
-impl ToOwned for Fqdn {
+#[cfg(feature = "alloc")]
+impl alloc::borrow::ToOwned for Fqdn {
     type Owned = FQDN;
     #[inline]
     fn to_owned(&self) -> FQDN { FQDN(self.0.to_owned()) }
 }
 
+impl Default for &Fqdn {
+    /// Gets the top domain.
+    #[inline]
+    fn default() -> Self {
+        // it is safe since the nul byte alone is the top domain
+        unsafe { Fqdn::from_bytes_unchecked(b"\x00") }
+    }
+}
+
 impl fmt::Display for Fqdn
 {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result
@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+use hickory_proto::rr::Name;
+
+use crate::check::{check_byte_sequence, check_rfc_lengths};
+use crate::{Error, Fqdn, FQDN};
+
+impl TryFrom<&Fqdn> for Name {
+    type Error = Error;
+
+    /// Builds a fully qualified hickory name, without going through its string representation.
+    ///
+    /// Since hickory names are limited as the RFC specifies, the conversion fails if a label
+    /// is longer than 63 chars ([`Error::TooLongLabel`]) or if the FQDN is longer than 255 chars
+    /// ([`Error::TooLongDomainName`]), which could only happen when the features
+    /// `domain-label-length-limited-to-63` and `domain-name-length-limited-to-255` are not activated.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use hickory_proto::rr::Name;
+    ///
+    /// let name = Name::try_from(fqdn!("rust-lang.github.io").as_ref())?;
+    /// assert![ name.is_fqdn() ];
+    /// assert_eq![ name.to_ascii(), "rust-lang.github.io." ];
+    /// # Ok::<(), Error>(())
+    /// ```
+    fn try_from(fqdn: &Fqdn) -> Result<Name, Self::Error>
+    {
+        check_rfc_lengths(fqdn.as_bytes())?;
+        // it never fails since the lengths were just checked
+        Ok(Name::from_labels(fqdn.labels().map(str::as_bytes))
+            .expect("a FQDN limited as the RFC specifies always fits in a hickory name"))
+    }
+}
+
+impl TryFrom<&FQDN> for Name {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(fqdn: &FQDN) -> Result<Name, Self::Error> { fqdn.as_ref().try_into() }
+}
+
+impl TryFrom<FQDN> for Name {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(fqdn: FQDN) -> Result<Name, Self::Error> { fqdn.as_ref().try_into() }
+}
+
+impl TryFrom<&Name> for FQDN {
+    type Error = Error;
+
+    /// Builds a FQDN from the labels of a hickory name.
+    ///
+    /// The labels are lowercased and checked with the same rules as any other
+    /// byte sequence (see [`Fqdn::from_bytes`]), so the returned error tells which rule failed.
+    /// A name which is not fully qualified is treated as a string without its
+    /// trailing dot: it is only rejected if the feature `domain-name-should-have-trailing-dot` is set.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use hickory_proto::rr::Name;
+    ///
+    /// let name = Name::from_ascii("Rust-Lang.GitHub.io.").unwrap();
+    /// assert_eq![ FQDN::try_from(&name), Ok(fqdn!("rust-lang.github.io")) ];
+    ///
+    /// let name = Name::from_ascii("rust_lang.github.io.").unwrap();
+    /// # #[cfg(feature = "domain-name-without-special-chars")]
+    /// assert_eq![ FQDN::try_from(&name), Err(Error::InvalidLabelChar) ];
+    /// ```
+    fn try_from(name: &Name) -> Result<FQDN, Self::Error>
+    {
+        #[cfg(feature = "domain-name-should-have-trailing-dot")]
+        if !name.is_fqdn() {
+            return Err(Error::TrailingDotMissing);
+        }
+
+        let mut bytes = Vec::with_capacity(name.len() + 2);
+        for label in name.iter() {
+            if label.len() > u8::MAX as usize {
+                return Err(Error::TooLongLabel);
+            }
+            bytes.push(label.len() as u8);
+            bytes.extend(label.iter().map(u8::to_ascii_lowercase));
+        }
+        bytes.push(0);
+
+        check_byte_sequence(&bytes)?;
+        // it is safe since the byte sequence was just checked
+        Ok(unsafe { FQDN::from_vec_with_nul_unchecked(bytes) })
+    }
+}
+
+impl TryFrom<Name> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(name: Name) -> Result<FQDN, Self::Error> { FQDN::try_from(&name) }
+}
+
+// a hickory name is equal to a FQDN if it would be converted to it
+// (the reverse comparisons conflict with the comparisons of FQDN with strings)
+fn eq_name(fqdn: &Fqdn, name: &Name) -> bool
+{
+    (cfg!(not(feature = "domain-name-should-have-trailing-dot")) || name.is_fqdn())
+        && name.num_labels() as usize == fqdn.depth()
+        && name.iter().zip(fqdn.labels()).all(|(a, b)| a.eq_ignore_ascii_case(b.as_bytes()))
+}
+
+impl PartialEq<Fqdn> for Name {
+    #[inline]
+    fn eq(&self, other: &Fqdn) -> bool { eq_name(other, self) }
+}
+
+impl PartialEq<FQDN> for Name {
+    #[inline]
+    fn eq(&self, other: &FQDN) -> bool { eq_name(other, self) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use hickory_proto::rr::Name;
+    use crate as fqdn;
+    use fqdn::*;
+
+    const NAMES: [&str; 6] = [".", "com.", "github.com.", "WWW.Rust-Lang.GitHub.io.", "_sip._tcp.example.org.", "xn--bcher-kva.example."];
+
+    #[test]
+    fn conversions()
+    {
+        for s in NAMES {
+            let name = Name::from_ascii(s).unwrap();
+            let fqdn = s.parse::<FQDN>();
+            assert_eq!(FQDN::try_from(&name), fqdn, "{s}");
+
+            if let Ok(fqdn) = fqdn {
+                assert_eq!(Name::try_from(&fqdn), Ok(name), "{s}");
+                assert_eq!(Name::try_from(&fqdn).unwrap().to_ascii(), s.to_ascii_lowercase(), "{s}");
+                assert_eq!(FQDN::try_from(Name::try_from(fqdn.clone()).unwrap()).as_ref(), Ok(&fqdn));
+            }
+        }
+
+        // hickory accepts any byte in a label
+        assert_eq!(FQDN::try_from(&Name::from_labels([&b"git@hub"[..], b"com"]).unwrap()), Err(Error::InvalidLabelChar));
+        assert_eq!(FQDN::try_from(&Name::from_labels([&b"github.com"[..], b"io"]).unwrap()), Err(Error::InvalidLabelChar));
+        #[cfg(feature = "domain-label-cannot-start-or-end-with-hyphen")]
+        assert_eq!(FQDN::try_from(&Name::from_labels([&b"-github"[..], b"com"]).unwrap()), Err(Error::LabelCannotStartWithHyphen));
+
+        // relative names
+        let relative = Name::from_ascii("github.com").unwrap();
+        #[cfg(feature = "domain-name-should-have-trailing-dot")]
+        assert_eq!(FQDN::try_from(&relative), Err(Error::TrailingDotMissing));
+        #[cfg(not(feature = "domain-name-should-have-trailing-dot"))]
+        assert_eq!(FQDN::try_from(&relative), Ok(fqdn!("github.com")));
+    }
+
+    #[test]
+    fn longest()
+    {
+        let label = "a".repeat(63);
+        let s = format!("{label}.{label}.{label}.{}.", "a".repeat(61));
+        let fqdn = s.parse::<FQDN>().unwrap();
+        assert_eq!(Name::try_from(&fqdn), Ok(Name::from_ascii(&s).unwrap()));
+        assert_eq!(FQDN::try_from(Name::try_from(&fqdn).unwrap()), Ok(fqdn));
+    }
+
+    #[test]
+    fn too_long()
+    {
+        // only reachable when the lengths are not limited by the features
+        let label = "a".repeat(64);
+        if let Ok(fqdn) = format!("{label}.com.").parse::<FQDN>() {
+            assert_eq!(Name::try_from(&fqdn), Err(Error::TooLongLabel));
+        }
+        let label = "a".repeat(63);
+        if let Ok(fqdn) = format!("{label}.{label}.{label}.{label}.").parse::<FQDN>() {
+            assert_eq!(Name::try_from(fqdn), Err(Error::TooLongDomainName));
+        }
+    }
+
+    #[test]
+    fn equality()
+    {
+        let name = Name::from_ascii("GitHub.com.").unwrap();
+        assert_eq!(name, fqdn!("github.com"));
+        assert_eq!(name, *fqdn!("github.com"));
+        assert_ne!(name, fqdn!("github.io"));
+        assert_ne!(name, fqdn!("www.github.com"));
+        assert_ne!(name, fqdn!("com"));
+        assert_eq!(Name::root(), FQDN::default());
+
+        #[cfg(not(feature = "domain-name-should-have-trailing-dot"))]
+        assert_eq!(Name::from_ascii("github.com").unwrap(), fqdn!("github.com"));
+        #[cfg(feature = "domain-name-should-have-trailing-dot")]
+        assert_ne!(Name::from_ascii("github.com").unwrap(), fqdn!("github.com"));
+    }
+}
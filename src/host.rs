@@ -0,0 +1,536 @@
+use alloc::format;
+use alloc::vec::Vec;
+use core::fmt;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
+
+use crate::{Error, FQDN};
+
+/// A host, as found in URL: a domain name or an IP address.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// use std::net::Ipv4Addr;
+///
+/// let host = Host::Ipv4(Ipv4Addr::LOCALHOST);
+/// assert_eq![ host.to_string(), "127.0.0.1" ];
+/// assert_eq![ host.as_fqdn(), None ];
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Host {
+    /// A domain name
+    Domain(FQDN),
+    /// An IPv4 address
+    Ipv4(Ipv4Addr),
+    /// An IPv6 address
+    Ipv6(Ipv6Addr),
+}
+
+impl Host {
+
+    /// Gets the domain name, if it is not an IP address.
+    #[inline]
+    pub fn as_fqdn(&self) -> Option<&FQDN>
+    {
+        match self {
+            Host::Domain(fqdn) => Some(fqdn),
+            _ => None
+        }
+    }
+
+    /// Gets the IP address, if it is not a domain name.
+    #[inline]
+    pub fn ip_addr(&self) -> Option<IpAddr>
+    {
+        match self {
+            Host::Domain(_) => None,
+            Host::Ipv4(ip) => Some(IpAddr::V4(*ip)),
+            Host::Ipv6(ip) => Some(IpAddr::V6(*ip)),
+        }
+    }
+}
+
+impl From<FQDN> for Host {
+    #[inline]
+    fn from(fqdn: FQDN) -> Self { Host::Domain(fqdn) }
+}
+
+impl From<IpAddr> for Host {
+    #[inline]
+    fn from(ip: IpAddr) -> Self
+    {
+        match ip {
+            IpAddr::V4(ip) => Host::Ipv4(ip),
+            IpAddr::V6(ip) => Host::Ipv6(ip),
+        }
+    }
+}
+
+impl TryFrom<Host> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(host: Host) -> Result<Self, Self::Error>
+    {
+        match host {
+            Host::Domain(fqdn) => Ok(fqdn),
+            _ => Err(Error::IpAddressLiteral)
+        }
+    }
+}
+
+impl FromStr for Host {
+    type Err = Error;
+
+    /// Parses a domain name or an IP address (IPv6 addresses could be enclosed in brackets).
+    ///
+    /// As for hosts of URL, the trailing dot of the domain name is optional.
+    ///
+    /// A FQDN which [looks like an IP literal](crate::Fqdn::looks_like_ip_literal)
+    /// but is not a valid IP address (e.g. `1.2.3.256` or `2130706433`) is rejected
+    /// with [`Error::InvalidIpAddress`], since it is ambiguous.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use std::net::{Ipv4Addr, Ipv6Addr};
+    ///
+    /// assert_eq![ "1.2.3.4".parse(), Ok(Host::Ipv4(Ipv4Addr::new(1, 2, 3, 4))) ];
+    /// assert_eq![ "[::1]".parse(), Ok(Host::Ipv6(Ipv6Addr::LOCALHOST)) ];
+    /// assert_eq![ "github.com".parse(), Ok(Host::Domain(fqdn!("github.com"))) ];
+    /// assert_eq![ "1.2.3.256".parse::<Host>(), Err(Error::InvalidIpAddress) ];
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        if let Some(ip) = s.strip_prefix('[') {
+            return ip.strip_suffix(']')
+                .and_then(|ip| ip.parse().ok())
+                .map(Host::Ipv6)
+                .ok_or(Error::InvalidIpAddress);
+        }
+        if let Ok(ip) = s.strip_suffix('.').unwrap_or(s).parse::<IpAddr>() {
+            return Ok(ip.into());
+        }
+        parse_host(s).map(Host::Domain)
+    }
+}
+
+impl fmt::Display for Host {
+    /// Formats the host as in an URL (IPv6 addresses are enclosed in brackets).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self {
+            Host::Domain(fqdn) => fqdn.fmt(f),
+            Host::Ipv4(ip) => ip.fmt(f),
+            Host::Ipv6(ip) => write!(f, "[{ip}]"),
+        }
+    }
+}
+
+impl FQDN {
+
+    /// Parses the value of a HTTP `Host` header (i.e. a host with an optional port).
+    ///
+    /// The trailing dot of the host is optional, whatever the activated features,
+    /// and the surrounding whitespaces are ignored. An empty port (e.g. `github.com:`)
+    /// is considered as missing, as RFC 3986 allows it.
+    ///
+    /// IP addresses, including bracketed IPv6 ones, are rejected with [`Error::IpAddressLiteral`]
+    /// (or with [`Error::InvalidIpAddress`] if the bracket is not closed).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// assert_eq![ FQDN::from_host_header("GitHub.com:8080"), Ok((fqdn!("github.com"), Some(8080))) ];
+    /// assert_eq![ FQDN::from_host_header("github.com."), Ok((fqdn!("github.com"), None)) ];
+    /// assert_eq![ FQDN::from_host_header("[::1]:8080"), Err(Error::IpAddressLiteral) ];
+    /// assert_eq![ FQDN::from_host_header("github.com:http"), Err(Error::InvalidPort) ];
+    /// ```
+    pub fn from_host_header(value: impl AsRef<[u8]>) -> Result<(FQDN, Option<u16>), Error>
+    {
+        let value = core::str::from_utf8(value.as_ref())
+            .map_err(|_| Error::InvalidLabelChar)?
+            .trim_matches([' ', '\t']);
+
+        let (host, port) = if value.starts_with('[') {
+            match value.split_once(']') {
+                Some((_, "")) => return Err(Error::IpAddressLiteral),
+                Some((_, port)) => match port.strip_prefix(':') {
+                    Some(port) => { parse_port(port)?; return Err(Error::IpAddressLiteral) }
+                    None => return Err(Error::InvalidPort)
+                }
+                // unterminated IPv6 address
+                None => return Err(Error::InvalidIpAddress)
+            }
+        } else {
+            match value.rsplit_once(':') {
+                None => (value, None),
+                // unbracketed IPv6 address
+                Some((host, _)) if host.contains(':') => {
+                    return Err(if value.parse::<IpAddr>().is_ok() { Error::IpAddressLiteral } else { Error::InvalidPort })
+                }
+                Some((host, port)) => (host, parse_port(port)?)
+            }
+        };
+        parse_host(host).map(|fqdn| (fqdn, port))
+    }
+}
+
+impl FQDN {
+
+    /// Builds the name used for reverse DNS lookups of an IP address (i.e. the `PTR` record).
+    ///
+    /// IPv4 addresses are mapped under `in-addr.arpa` as specified by RFC 1035
+    /// and IPv6 addresses under `ip6.arpa` (nibble by nibble) as specified by RFC 3596.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use std::net::{Ipv4Addr, Ipv6Addr};
+    ///
+    /// assert_eq![ FQDN::reverse_pointer(Ipv4Addr::new(192, 0, 2, 1).into()), fqdn!("1.2.0.192.in-addr.arpa") ];
+    /// assert_eq![ FQDN::reverse_pointer(Ipv6Addr::LOCALHOST.into()).tld(), Some(fqdn!("arpa").as_ref()) ];
+    /// ```
+    pub fn reverse_pointer(ip: IpAddr) -> FQDN
+    {
+        let mut bytes = Vec::with_capacity(73);
+        match ip {
+            IpAddr::V4(ip) => {
+                for octet in ip.octets().iter().rev() {
+                    let label = format!("{octet}");
+                    bytes.push(label.len() as u8);
+                    bytes.extend_from_slice(label.as_bytes());
+                }
+                bytes.extend_from_slice(b"\x07in-addr\x04arpa\x00");
+            }
+            IpAddr::V6(ip) => {
+                const HEX: &[u8; 16] = b"0123456789abcdef";
+                for octet in ip.octets().iter().rev() {
+                    bytes.extend_from_slice(&[1, HEX[(octet & 0xf) as usize], 1, HEX[(octet >> 4) as usize]]);
+                }
+                bytes.extend_from_slice(b"\x03ip6\x04arpa\x00");
+            }
+        }
+        // it is safe since the labels are made of digits and lowercase letters
+        // and the name is far below the length limits
+        unsafe { FQDN::from_vec_with_nul_unchecked(bytes) }
+    }
+}
+
+#[cfg(feature = "url")]
+impl FQDN {
+
+    /// Parses a host as browsers do, following the host parser of the
+    /// [WHATWG URL Standard](https://url.spec.whatwg.org/#host-parsing).
+    ///
+    /// The host is percent-decoded and mapped with the UTS #46 processing (so that
+    /// internationalized names are converted to punycode) and the forbidden code points
+    /// are rejected. Hosts ending with a number (e.g. `2130706433` or `0x7f.1`) are
+    /// parsed as IPv4 addresses and bracketed ones as IPv6 addresses.
+    ///
+    /// Since a FQDN is more restricted than a WHATWG domain, some hosts accepted by
+    /// browsers are still rejected here (e.g. `a*b.com` with [`Error::InvalidLabelChar`]).
+    /// A trailing dot is always optional.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// assert_eq![ FQDN::parse_url_host("2130706433"), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)) ];
+    /// assert_eq![ FQDN::parse_url_host("0x7f.1"), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)) ];
+    /// assert_eq![ FQDN::parse_url_host("%47ithub.COM"), Ok(Host::Domain(fqdn!("github.com"))) ];
+    /// assert_eq![ FQDN::parse_url_host("bücher.example"), Ok(Host::Domain(fqdn!("xn--bcher-kva.example"))) ];
+    /// assert_eq![ FQDN::parse_url_host("git<hub.com"), Err(Error::InvalidLabelChar) ];
+    /// ```
+    pub fn parse_url_host(s: &str) -> Result<Host, Error>
+    {
+        match url::Host::parse(s) {
+            Ok(url::Host::Domain(domain)) => crate::__private::parse_dotted(&format!("{domain}.")).map(Host::Domain),
+            Ok(url::Host::Ipv4(ip)) => Ok(Host::Ipv4(ip)),
+            Ok(url::Host::Ipv6(ip)) => Ok(Host::Ipv6(ip)),
+            Err(url::ParseError::EmptyHost) => Err(Error::MissingHost),
+            Err(url::ParseError::InvalidIpv4Address | url::ParseError::InvalidIpv6Address) => Err(Error::InvalidIpAddress),
+            Err(_) => Err(Error::InvalidLabelChar),
+        }
+    }
+}
+
+// Parses a host, without any port, with or without its trailing dot
+pub(crate) fn parse_host(host: &str) -> Result<FQDN, Error>
+{
+    let name = host.strip_suffix('.').unwrap_or(host);
+    if name.is_empty() {
+        Err(Error::MissingHost)
+    } else if host.starts_with('[') || name.parse::<IpAddr>().is_ok() {
+        Err(Error::IpAddressLiteral)
+    } else {
+        match crate::__private::parse_dotted(&format!("{name}.")) {
+            // browsers would try to parse it as an IPv4 address
+            Ok(fqdn) if fqdn.looks_like_ip_literal() => Err(Error::InvalidIpAddress),
+            #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+            Err(Error::NumericTopLevelDomain) => Err(Error::InvalidIpAddress),
+            res => res
+        }
+    }
+}
+
+fn parse_port(port: &str) -> Result<Option<u16>, Error>
+{
+    match port {
+        "" => Ok(None),
+        port if port.bytes().all(|b| b.is_ascii_digit()) => port.parse().map(Some).map_err(|_| Error::InvalidPort),
+        _ => Err(Error::InvalidPort)
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<&url::Url> for FQDN {
+    type Error = Error;
+
+    /// Extracts the host of an URL.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let url = url::Url::parse("https://user@GitHub.com.:8080/rust-lang").unwrap();
+    /// assert_eq![ FQDN::try_from(&url), Ok(fqdn!("github.com")) ];
+    ///
+    /// let url = url::Url::parse("https://127.0.0.1/").unwrap();
+    /// assert_eq![ FQDN::try_from(&url), Err(Error::IpAddressLiteral) ];
+    /// ```
+    #[inline]
+    fn try_from(url: &url::Url) -> Result<Self, Self::Error>
+    {
+        url.host().ok_or(Error::MissingHost).and_then(FQDN::try_from)
+    }
+}
+
+#[cfg(feature = "url")]
+impl TryFrom<url::Host<&str>> for FQDN {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(host: url::Host<&str>) -> Result<Self, Self::Error>
+    {
+        match host {
+            // the host of an URL with a non special scheme is not parsed, so it could be an IP address
+            url::Host::Domain(domain) => parse_host(domain),
+            url::Host::Ipv4(_) | url::Host::Ipv6(_) => Err(Error::IpAddressLiteral)
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<&http::uri::Authority> for FQDN {
+    type Error = Error;
+
+    /// Extracts the host of an URI authority (the user info and the port are ignored).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let authority = "user@GitHub.com:8080".parse::<http::uri::Authority>().unwrap();
+    /// assert_eq![ FQDN::try_from(&authority), Ok(fqdn!("github.com")) ];
+    ///
+    /// let authority = "[::1]:8080".parse::<http::uri::Authority>().unwrap();
+    /// assert_eq![ FQDN::try_from(&authority), Err(Error::IpAddressLiteral) ];
+    /// ```
+    #[inline]
+    fn try_from(authority: &http::uri::Authority) -> Result<Self, Self::Error> { parse_host(authority.host()) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn host_header()
+    {
+        let github = fqdn!("github.com");
+        assert_eq!(FQDN::from_host_header("github.com"), Ok((github.clone(), None)));
+        assert_eq!(FQDN::from_host_header(" GitHub.COM.:443\t"), Ok((github.clone(), Some(443))));
+        assert_eq!(FQDN::from_host_header(b"github.com:"), Ok((github.clone(), None)));
+        assert_eq!(FQDN::from_host_header("github.com:0065535"), Ok((github.clone(), Some(65535))));
+
+        assert_eq!(FQDN::from_host_header(""), Err(Error::MissingHost));
+        assert_eq!(FQDN::from_host_header(":80"), Err(Error::MissingHost));
+        assert_eq!(FQDN::from_host_header("."), Err(Error::MissingHost));
+        assert_eq!(FQDN::from_host_header("github.com:65536"), Err(Error::InvalidPort));
+        assert_eq!(FQDN::from_host_header("github.com:+80"), Err(Error::InvalidPort));
+        assert_eq!(FQDN::from_host_header("github.com:80:80"), Err(Error::InvalidPort));
+        assert_eq!(FQDN::from_host_header("git hub.com"), Err(Error::InvalidLabelChar));
+        assert_eq!(FQDN::from_host_header(b"github.\xffcom"), Err(Error::InvalidLabelChar));
+        assert_eq!(FQDN::from_host_header("github..com"), Err(Error::EmptyLabel));
+
+        for ip in ["127.0.0.1", "127.0.0.1.:80", "[::1]", "[::1]:8080", "::1", "[fe80::1%25eth0]"] {
+            assert_eq!(FQDN::from_host_header(ip), Err(Error::IpAddressLiteral), "{ip}");
+        }
+        assert_eq!(FQDN::from_host_header("[::1]8080"), Err(Error::InvalidPort));
+        assert_eq!(FQDN::from_host_header("2130706433:80"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::from_host_header("1.2.3.256"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::from_host_header("[::1"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::from_host_header("[::1:8080"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::from_host_header("[::1]:http"), Err(Error::InvalidPort));
+    }
+
+    #[test]
+    fn host()
+    {
+        use std::net::{Ipv4Addr, Ipv6Addr};
+
+        assert_eq!("127.0.0.1".parse(), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)));
+        assert_eq!("127.0.0.1.".parse(), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)));
+        assert_eq!("::1".parse(), Ok(Host::Ipv6(Ipv6Addr::LOCALHOST)));
+        assert_eq!("[::1]".parse(), Ok(Host::Ipv6(Ipv6Addr::LOCALHOST)));
+        assert_eq!("[::ffff:1.2.3.4]".parse(), Ok(Host::Ipv6(Ipv4Addr::new(1, 2, 3, 4).to_ipv6_mapped())));
+        assert_eq!("GitHub.com.".parse(), Ok(Host::Domain(fqdn!("github.com"))));
+        assert_eq!("1.2.3.4.example.".parse(), Ok(Host::Domain(fqdn!("1.2.3.4.example"))));
+
+        for s in ["1.2.3.256", "2130706433.", "0x7f.1.", "github.0x1f.", "[::1", "[1.2.3.4]", "[github.com]"] {
+            assert_eq!(s.parse::<Host>(), Err(Error::InvalidIpAddress), "{s}");
+        }
+        assert_eq!("git@hub.com.".parse::<Host>(), Err(Error::InvalidLabelChar));
+
+        let host = "[::1]".parse::<Host>().unwrap();
+        assert_eq!(host.to_string(), "[::1]");
+        assert_eq!(host.ip_addr(), Some(Ipv6Addr::LOCALHOST.into()));
+        assert_eq!(FQDN::try_from(host), Err(Error::IpAddressLiteral));
+        assert_eq!(FQDN::try_from(Host::from(fqdn!("github.com"))), Ok(fqdn!("github.com")));
+    }
+
+    #[test]
+    fn reverse_pointer()
+    {
+        use std::net::{Ipv4Addr, Ipv6Addr};
+
+        assert_eq!(FQDN::reverse_pointer(Ipv4Addr::new(10, 0, 255, 1).into()), fqdn!("1.255.0.10.in-addr.arpa"));
+        let ptr = FQDN::reverse_pointer(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0x567, 0x89ab).into());
+        assert_eq!(ptr, fqdn!("b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"));
+        assert_eq!(Fqdn::from_bytes(ptr.as_bytes()), Ok(ptr.as_ref()));
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url()
+    {
+        let parse = |s| FQDN::try_from(&url::Url::parse(s).unwrap());
+        assert_eq!(parse("https://GitHub.com/rust-lang"), Ok(fqdn!("github.com")));
+        assert_eq!(parse("https://github.com.:8080/"), Ok(fqdn!("github.com")));
+        assert_eq!(parse("ssh://git@github.com/rust-lang/rust.git"), Ok(fqdn!("github.com")));
+        assert_eq!(parse("https://0x7f.1/"), Err(Error::IpAddressLiteral));
+        assert_eq!(parse("https://[::1]/"), Err(Error::IpAddressLiteral));
+        assert_eq!(parse("ssh://127.0.0.1/"), Err(Error::IpAddressLiteral));
+        assert_eq!(parse("ssh://[::1]/"), Err(Error::IpAddressLiteral));
+        assert_eq!(parse("mailto:git@github.com"), Err(Error::MissingHost));
+        assert_eq!(parse("file:///etc/hosts"), Err(Error::MissingHost));
+        assert_eq!(FQDN::try_from(url::Host::Domain("crates.io")), Ok(fqdn!("crates.io")));
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_host()
+    {
+        use std::net::{Ipv4Addr, Ipv6Addr};
+
+        assert_eq!(FQDN::parse_url_host("2130706433"), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)));
+        assert_eq!(FQDN::parse_url_host("0x7f.0.0.1."), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)));
+        assert_eq!(FQDN::parse_url_host("0177.1"), Ok(Host::Ipv4(Ipv4Addr::LOCALHOST)));
+        assert_eq!(FQDN::parse_url_host("[::1]"), Ok(Host::Ipv6(Ipv6Addr::LOCALHOST)));
+        assert_eq!(FQDN::parse_url_host("ＧｉｔＨｕｂ.com"), Ok(Host::Domain(fqdn!("github.com"))));
+        assert_eq!(FQDN::parse_url_host("github.com."), Ok(Host::Domain(fqdn!("github.com"))));
+        assert_eq!(FQDN::parse_url_host("1.2.3.256"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::parse_url_host("github.0x"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::parse_url_host("[::1"), Err(Error::InvalidIpAddress));
+        assert_eq!(FQDN::parse_url_host(""), Err(Error::MissingHost));
+        assert_eq!(FQDN::parse_url_host("git%20hub.com"), Err(Error::InvalidLabelChar));
+        assert_eq!(FQDN::parse_url_host("git$hub.com"), Err(Error::InvalidLabelChar));
+    }
+
+    // the host cases of the URL tests of the web-platform-tests project
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_host_wpt()
+    {
+        // only absolute URL with a special scheme (except 'file:') are relevant
+        const SPECIAL: [&str; 5] = ["http:", "https:", "ws:", "wss:", "ftp:"];
+
+        // extracts the host and the port of an absolute URL, as written in the input
+        fn authority<'a>(input: &'a str, protocol: &str) -> Option<(&'a str, Option<&'a str>)>
+        {
+            let authority = input.get(protocol.len()..)
+                .filter(|_| input[..protocol.len()].eq_ignore_ascii_case(protocol))
+                .and_then(|s| s.strip_prefix("//").or_else(|| s.strip_prefix("\\\\")))?;
+            let authority = authority.split(['/', '\\', '?', '#']).next().unwrap();
+            let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+            Some(match host.find(']') {
+                Some(end) => (&host[..=end], host[end + 1..].strip_prefix(':')),
+                None => host.split_once(':').map_or((host, None), |(host, port)| (host, Some(port))),
+            })
+        }
+
+        // the test data is not packaged with the crate, so the test is skipped without it
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/urltestdata.json");
+        let Ok(tests) = std::fs::read_to_string(path) else {
+            eprintln!("{path} is missing: skipping the WPT tests");
+            return
+        };
+        let tests: serde_json::Value = serde_json::from_str(&tests).unwrap();
+        let (mut checked, mut failures) = (0, 0);
+
+        for test in tests.as_array().unwrap().iter().filter_map(|t| t.as_object()) {
+            let Some(input) = test["input"].as_str() else { continue };
+            let input = input.trim_matches(|c: char| c <= ' ').replace(['\t', '\n', '\r'], "");
+
+            if test.get("failure").and_then(|f| f.as_bool()) == Some(true) {
+                // the URL is invalid because of its host, unless its port is invalid
+                let Some((host, port)) = SPECIAL.iter().find_map(|protocol| authority(&input, protocol)) else {
+                    continue
+                };
+                if port.is_some_and(|port| !port.is_empty() && port.parse::<u16>().is_err()) {
+                    continue
+                }
+                assert!(FQDN::parse_url_host(host).is_err(), "{input}");
+                failures += 1;
+                continue
+            }
+
+            let (Some(protocol), Some(hostname)) = (test.get("protocol"), test.get("hostname")) else {
+                continue
+            };
+            let protocol = protocol.as_str().unwrap();
+            if !SPECIAL.contains(&protocol) {
+                continue
+            }
+            let Some((host, _)) = authority(&input, protocol) else { continue };
+
+            let hostname = hostname.as_str().unwrap();
+            let expected = match crate::__private::parse_dotted(&format!("{hostname}.")) {
+                _ if hostname.starts_with('[') || hostname.parse::<std::net::Ipv4Addr>().is_ok() => Ok(hostname.to_string()),
+                Ok(fqdn) => Ok(fqdn.to_string()),
+                Err(e) => Err(e)
+            };
+            assert_eq!(FQDN::parse_url_host(host).map(|h| h.to_string()), expected, "{input}");
+            checked += 1;
+        }
+        assert!(checked > 150, "only {checked} hosts checked");
+        assert!(failures > 150, "only {failures} invalid hosts checked");
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn authority()
+    {
+        let parse = |s: &str| FQDN::try_from(&s.parse::<http::uri::Authority>().unwrap());
+        assert_eq!(parse("github.com"), Ok(fqdn!("github.com")));
+        assert_eq!(parse("user:pass@GitHub.com.:443"), Ok(fqdn!("github.com")));
+        assert_eq!(parse("127.0.0.1:80"), Err(Error::IpAddressLiteral));
+        assert_eq!(parse("[::1]"), Err(Error::IpAddressLiteral));
+
+        let value = http::HeaderValue::from_static("github.com:8080");
+        assert_eq!(FQDN::from_host_header(&value), Ok((fqdn!("github.com"), Some(8080))));
+    }
+}
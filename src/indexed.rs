@@ -0,0 +1,284 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::ops;
+use core::str::FromStr;
+
+use crate::{Error, Fqdn, FQDN};
+
+/// A FQDN with a precomputed index of its labels.
+///
+/// Most of the methods of [`Fqdn`] walk the length bytes from the start of the FQDN
+/// on each call. This structure caches the start offset of each label so that
+/// [`depth`](Self::depth), [`label`](Self::label), [`suffix`](Self::suffix) and
+/// [`parent`](Self::parent) are done in O(1) and [`labels`](Self::labels)
+/// could be iterated in both directions at no cost.
+///
+/// A hash of the FQDN is also precomputed, so hashing an `IndexedFqdn` only
+/// feeds the hasher with a single `u64` and comparing two different FQDN usually
+/// stops at this hash. As a consequence, `IndexedFqdn` does not implement
+/// [`Borrow<Fqdn>`](core::borrow::Borrow) since their hashes differ.
+///
+/// As [`FQDN`], it dereferences to [`&Fqdn`](crate::Fqdn).
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let fqdn = IndexedFqdn::from(fqdn!("www.rust-lang.github.io"));
+/// assert_eq![ fqdn.depth(), 4 ];
+/// assert_eq![ fqdn.label(1), Some("rust-lang") ];
+/// assert_eq![ fqdn.suffix(2), Some(fqdn!("github.io").as_ref()) ];
+/// assert![ fqdn.labels().rev().eq(["io", "github", "rust-lang", "www"]) ];
+/// ```
+#[derive(Clone)]
+pub struct IndexedFqdn {
+    fqdn: FQDN,
+    // start offset of each label, from the leftmost one
+    offsets: Box<[u32]>,
+    hash: u64,
+}
+
+impl IndexedFqdn {
+
+    /// Builds the index of a FQDN.
+    pub fn new(fqdn: FQDN) -> Self
+    {
+        let bytes = fqdn.as_bytes();
+        let offsets = fqdn.hierarchy()
+            .map(|suffix| (bytes.len() - suffix.as_bytes().len()) as u32)
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        // FNV-1a, which is good enough for the short strings of FQDN
+        let hash = bytes.iter()
+            .fold(0xcbf29ce484222325_u64, |hash, &b| (hash ^ b as u64).wrapping_mul(0x100000001b3));
+
+        IndexedFqdn { fqdn, offsets, hash }
+    }
+
+    /// Gets the indexed FQDN.
+    #[inline]
+    pub fn as_fqdn(&self) -> &Fqdn { &self.fqdn }
+
+    /// Gets back the owned FQDN, dropping the index.
+    #[inline]
+    pub fn into_fqdn(self) -> FQDN { self.fqdn }
+
+    /// Gets the precomputed hash of the FQDN.
+    ///
+    /// This hash does not depend on any random state, so it is stable between runs.
+    #[inline]
+    pub fn precomputed_hash(&self) -> u64 { self.hash }
+
+    /// Computes the depth of this domain (i.e. counts the labels) in O(1).
+    #[inline]
+    pub fn depth(&self) -> usize { self.offsets.len() }
+
+    /// Gets the label at the given position, starting from the leftmost one, in O(1).
+    ///
+    /// If the position is out of range, `None` is returned.
+    #[inline]
+    pub fn label(&self, i: usize) -> Option<&str>
+    {
+        self.offsets.get(i).map(|&start| self.label_at(start as usize))
+    }
+
+    /// Extracts the suffix of this FQDN made of its last `n` labels in O(1).
+    ///
+    /// The suffix of length 0 is the top domain and the suffix of length [`Self::depth`]
+    /// is the FQDN itself. If `n` is greater than the depth, `None` is returned.
+    #[inline]
+    pub fn suffix(&self, n: usize) -> Option<&Fqdn>
+    {
+        let bytes = self.fqdn.as_bytes();
+        let start = match n {
+            0 => bytes.len() - 1,
+            n => *self.offsets.get(self.depth().checked_sub(n)?)? as usize
+        };
+        // it is safe since start is the start of a label (or the trailing nul byte)
+        Some(unsafe { Fqdn::from_bytes_unchecked(&bytes[start..]) })
+    }
+
+    /// Extracts the immediate parent domain in O(1).
+    ///
+    /// As for [`Fqdn::parent`], `None` is returned for a top level domain and for the top domain.
+    #[inline]
+    pub fn parent(&self) -> Option<&Fqdn>
+    {
+        if self.depth() <= 1 { None } else { self.suffix(self.depth() - 1) }
+    }
+
+    /// Gets the top level domain in O(1).
+    ///
+    /// If the FQDN is the top domain, `None` is returned.
+    #[inline]
+    pub fn tld(&self) -> Option<&Fqdn>
+    {
+        if self.fqdn.is_root() { None } else { self.suffix(1) }
+    }
+
+    /// Iterates over the labels, in both directions at no cost.
+    #[inline]
+    pub fn labels(&self) -> IndexedLabels<'_> { IndexedLabels { fqdn: self, offsets: self.offsets.iter() } }
+
+    #[inline]
+    fn label_at(&self, start: usize) -> &str
+    {
+        let bytes = self.fqdn.as_bytes();
+        // it is safe because a FQDN contains only ASCII characters
+        unsafe { core::str::from_utf8_unchecked(&bytes[start + 1..=start + bytes[start] as usize]) }
+    }
+}
+
+impl From<FQDN> for IndexedFqdn {
+    #[inline]
+    fn from(fqdn: FQDN) -> Self { Self::new(fqdn) }
+}
+
+impl From<&Fqdn> for IndexedFqdn {
+    #[inline]
+    fn from(fqdn: &Fqdn) -> Self { Self::new(fqdn.into()) }
+}
+
+impl From<IndexedFqdn> for FQDN {
+    #[inline]
+    fn from(fqdn: IndexedFqdn) -> Self { fqdn.into_fqdn() }
+}
+
+impl FromStr for IndexedFqdn {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> { s.parse::<FQDN>().map(Self::new) }
+}
+
+impl AsRef<Fqdn> for IndexedFqdn {
+    #[inline]
+    fn as_ref(&self) -> &Fqdn { &self.fqdn }
+}
+
+impl ops::Deref for IndexedFqdn {
+    type Target = Fqdn;
+    #[inline]
+    fn deref(&self) -> &Fqdn { &self.fqdn }
+}
+
+impl Hash for IndexedFqdn {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) { state.write_u64(self.hash) }
+}
+
+impl PartialEq for IndexedFqdn {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { self.hash == other.hash && self.fqdn == other.fqdn }
+}
+
+impl Eq for IndexedFqdn { }
+
+impl PartialEq<Fqdn> for IndexedFqdn {
+    #[inline]
+    fn eq(&self, other: &Fqdn) -> bool { self.fqdn.eq(other) }
+}
+
+impl PartialEq<FQDN> for IndexedFqdn {
+    #[inline]
+    fn eq(&self, other: &FQDN) -> bool { self.fqdn.eq(other) }
+}
+
+impl fmt::Display for IndexedFqdn {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.fqdn.fmt(f) }
+}
+
+impl fmt::Debug for IndexedFqdn {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_tuple("IndexedFqdn").field(&self.fqdn).finish()
+    }
+}
+
+/// An iterator over the labels of an [`IndexedFqdn`].
+///
+/// This struct is created by [`IndexedFqdn::labels`].
+#[derive(Debug, Clone)]
+pub struct IndexedLabels<'a> {
+    fqdn: &'a IndexedFqdn,
+    offsets: core::slice::Iter<'a, u32>,
+}
+
+impl<'a> Iterator for IndexedLabels<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> { self.offsets.next().map(|&start| self.fqdn.label_at(start as usize)) }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.offsets.size_hint() }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> { self.offsets.nth(n).map(|&start| self.fqdn.label_at(start as usize)) }
+}
+
+impl DoubleEndedIterator for IndexedLabels<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> { self.offsets.next_back().map(|&start| self.fqdn.label_at(start as usize)) }
+}
+
+impl ExactSizeIterator for IndexedLabels<'_> { }
+
+impl FusedIterator for IndexedLabels<'_> { }
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn index()
+    {
+        let fqdn = fqdn!("www.rust-lang.github.io");
+        let indexed = IndexedFqdn::from(fqdn.as_ref());
+
+        assert_eq!(indexed.depth(), fqdn.depth());
+        assert!((0..=5).all(|i| indexed.label(i) == fqdn.label(i)));
+        assert!((0..=5).all(|n| indexed.suffix(n) == fqdn.suffix(n)));
+        assert_eq!(indexed.parent(), fqdn.parent());
+        assert_eq!(indexed.tld(), fqdn.tld());
+        assert!(indexed.labels().eq(fqdn.labels()));
+        assert!(indexed.labels().rev().eq(fqdn.labels().rev()));
+        assert_eq!(indexed.labels().nth(3), Some("io"));
+        assert_eq!(indexed.to_string(), fqdn.to_string());
+        assert_eq!(FQDN::from(indexed), fqdn);
+
+        let root = IndexedFqdn::from(FQDN::default());
+        assert_eq!(root.depth(), 0);
+        assert_eq!(root.label(0), None);
+        assert_eq!(root.suffix(0), Some(FQDN::default().as_ref()));
+        assert_eq!(root.suffix(1), None);
+        assert_eq!(root.parent(), None);
+        assert_eq!(root.parent(), root.as_ref().parent());
+        assert_eq!(root.tld(), None);
+
+        let tld = IndexedFqdn::from(fqdn!("com"));
+        assert_eq!(tld.parent(), None);
+        assert_eq!(tld.parent(), fqdn!("com").parent());
+        assert_eq!(tld.tld(), Some(fqdn!("com").as_ref()));
+    }
+
+    #[test]
+    fn hash()
+    {
+        let a = IndexedFqdn::from(fqdn!("github.com"));
+        let b = "GitHub.COM.".parse::<IndexedFqdn>().unwrap();
+        let c = IndexedFqdn::from(fqdn!("github.io"));
+        assert_eq!(a, b);
+        assert_eq!(a.precomputed_hash(), b.precomputed_hash());
+        assert_ne!(a, c);
+        assert_eq!([a, b, c].into_iter().collect::<HashSet<_>>().len(), 2);
+    }
+}
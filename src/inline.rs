@@ -0,0 +1,260 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops;
+use core::str::FromStr;
+
+use crate::check::check_and_lower_any_char;
+use crate::{Error, Fqdn};
+
+/// The maximum length of the byte sequence of an [`InlineFqdn`] (including the trailing nul byte).
+pub const INLINE_CAPACITY: usize = 255;
+
+/// A FQDN stored inline, without any allocation.
+///
+/// The byte sequence is stored in a fixed-size array of [`INLINE_CAPACITY`] bytes,
+/// which is enough for any FQDN compliant with the RFC 1035 (see the feature
+/// `domain-name-length-limited-to-255`). Longer FQDN are rejected with
+/// [`Error::TooLongDomainName`], whatever the activated features.
+///
+/// As [`FQDN`](crate::FQDN), it dereferences to [`&Fqdn`](crate::Fqdn) but, since
+/// it is [`Copy`], it is well suited to parse a lot of names in a hot path.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// # #[cfg(feature = "alloc")] {
+/// let inline = "github.com.".parse::<InlineFqdn>().unwrap();
+/// let copy = inline;
+/// assert_eq![ inline.depth(), 2 ];
+/// assert_eq![ *copy, fqdn!("github.com") ];
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct InlineFqdn {
+    // length of the byte sequence, including the trailing nul byte
+    len: u8,
+    bytes: [u8; INLINE_CAPACITY],
+}
+
+impl InlineFqdn {
+
+    /// Builds an inline FQDN from a byte sequence.
+    ///
+    /// The rules are the same as for [`Fqdn::from_bytes`].
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// assert_eq![ InlineFqdn::from_bytes(b"\x06crates\x02io\x00").map(|f| f.depth()), Ok(2) ];
+    /// assert_eq![ InlineFqdn::from_bytes(b"\x06cr@tes\x02io\x00"), Err(Error::InvalidLabelChar) ];
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error>
+    {
+        Fqdn::from_bytes(bytes).and_then(Self::try_from)
+    }
+
+    /// Builds an inline FQDN from an ascii string.
+    ///
+    /// The rules are the same as for [`FQDN::from_ascii_str`](crate::FQDN::from_ascii_str).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// assert_eq![ InlineFqdn::from_ascii_str("crates.io.").map(|f| f.depth()), Ok(2) ];
+    /// assert_eq![ InlineFqdn::from_ascii_str("crates..io."), Err(Error::EmptyLabel) ];
+    /// ```
+    pub fn from_ascii_str(s: &str) -> Result<Self, Error>
+    {
+        let mut fqdn = Self::default();
+
+        // check the trailing dot and remove it
+        // (the empty FQDN '.' is also managed here)
+        let s = s.as_bytes();
+        let toparse = match s.last() {
+            None => {
+                #[cfg(feature = "domain-name-should-have-trailing-dot")]
+                return Err(Error::TrailingDotMissing);
+                #[cfg(not(feature = "domain-name-should-have-trailing-dot"))]
+                return Ok(fqdn);
+            }
+            Some(&b'.') => {
+                // ok, there is a trailing dot
+                if s.len() == 1 {
+                    return Ok(fqdn);
+                }
+                &s[..s.len() - 1]
+            }
+            _ => {
+                #[cfg(feature = "domain-name-should-have-trailing-dot")]
+                return Err(Error::TrailingDotMissing);
+                #[cfg(not(feature = "domain-name-should-have-trailing-dot"))]
+                s // no trailing dot to remove
+            }
+        };
+
+        // check against 253 since we have the trailing char and the first label length to consider
+        if toparse.len() > INLINE_CAPACITY - 2 {
+            return Err(Error::TooLongDomainName);
+        }
+
+        // the labels are shifted by one byte to prepend their length
+        let mut start = 0;
+        for label in toparse.split(|&c| c == b'.') {
+            match label.len() {
+                #[cfg(feature = "domain-label-length-limited-to-63")]
+                l if l > 63 => return Err(Error::TooLongLabel),
+
+                0 => return Err(Error::EmptyLabel),
+
+                l => {
+                    fqdn.bytes[start] = l as u8;
+                    let dest = &mut fqdn.bytes[start + 1..=start + l];
+                    for (b, &c) in dest.iter_mut().zip(label) {
+                        *b = check_and_lower_any_char(c)?;
+                    }
+                    #[cfg(feature = "domain-label-cannot-start-or-end-with-hyphen")]
+                    {
+                        if dest[0] == b'-' {
+                            return Err(Error::LabelCannotStartWithHyphen);
+                        }
+                        if dest[l - 1] == b'-' {
+                            return Err(Error::LabelCannotEndWithHyphen);
+                        }
+                    }
+                    start += l + 1;
+                }
+            }
+        }
+        fqdn.len = (toparse.len() + 2) as u8;
+        #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+        if crate::check::has_numeric_tld(&fqdn.bytes) {
+            return Err(Error::NumericTopLevelDomain);
+        }
+        Ok(fqdn)
+    }
+}
+
+impl Default for InlineFqdn {
+    /// Builds the top domain.
+    #[inline]
+    fn default() -> Self { Self { len: 1, bytes: [0; INLINE_CAPACITY] } }
+}
+
+impl AsRef<Fqdn> for InlineFqdn {
+    #[inline]
+    fn as_ref(&self) -> &Fqdn {
+        // SAFE because the byte sequence was checked at build time
+        unsafe { Fqdn::from_bytes_unchecked(&self.bytes[..self.len as usize]) }
+    }
+}
+
+impl ops::Deref for InlineFqdn {
+    type Target = Fqdn;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl Borrow<Fqdn> for InlineFqdn {
+    #[inline]
+    fn borrow(&self) -> &Fqdn {
+        self.as_ref()
+    }
+}
+
+impl TryFrom<&Fqdn> for InlineFqdn {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(fqdn: &Fqdn) -> Result<Self, Self::Error>
+    {
+        let bytes = fqdn.as_bytes();
+        if bytes.len() > INLINE_CAPACITY {
+            Err(Error::TooLongDomainName)
+        } else {
+            let mut inline = Self { len: bytes.len() as u8, bytes: [0; INLINE_CAPACITY] };
+            inline.bytes[..bytes.len()].copy_from_slice(bytes);
+            Ok(inline)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<InlineFqdn> for crate::FQDN {
+    #[inline]
+    fn from(fqdn: InlineFqdn) -> Self { fqdn.as_ref().into() }
+}
+
+impl FromStr for InlineFqdn {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "punycode")]
+        if !s.is_ascii() {
+            return crate::FQDN::punyencode(s).and_then(|fqdn| Self::try_from(fqdn.as_ref()));
+        }
+        Self::from_ascii_str(s)
+    }
+}
+
+impl fmt::Display for InlineFqdn {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl fmt::Debug for InlineFqdn {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("InlineFqdn").field(&self.as_ref()).finish()
+    }
+}
+
+impl Hash for InlineFqdn {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) { self.as_ref().hash(state) }
+}
+
+impl PartialEq for InlineFqdn {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { self.as_ref().eq(other.as_ref()) }
+}
+
+impl Eq for InlineFqdn { }
+
+impl PartialEq<Fqdn> for InlineFqdn {
+    #[inline]
+    fn eq(&self, other: &Fqdn) -> bool { self.as_ref().eq(other) }
+}
+
+impl PartialEq<InlineFqdn> for Fqdn {
+    #[inline]
+    fn eq(&self, other: &InlineFqdn) -> bool { self.eq(other.as_ref()) }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<crate::FQDN> for InlineFqdn {
+    #[inline]
+    fn eq(&self, other: &crate::FQDN) -> bool { self.as_ref().eq(other.as_ref()) }
+}
+
+#[cfg(feature = "alloc")]
+impl PartialEq<InlineFqdn> for crate::FQDN {
+    #[inline]
+    fn eq(&self, other: &InlineFqdn) -> bool { self.as_ref().eq(other.as_ref()) }
+}
+
+impl PartialOrd for InlineFqdn {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for InlineFqdn {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering { self.as_ref().cmp(other.as_ref()) }
+}
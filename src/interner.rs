@@ -0,0 +1,278 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use crate::Fqdn;
+
+/// A pool of FQDN where each distinct name is stored only once.
+///
+/// Interning a FQDN returns an [`Interned`] handle which is cheap to clone and
+/// dereferences to [`&Fqdn`](crate::Fqdn). Two handles coming from the same interner
+/// are equal if and only if they point to the same FQDN, so the comparison is done in O(1).
+///
+/// The interner is thread-safe: it could be shared between threads or, more simply,
+/// the process-wide [`FqdnInterner::global`] could be used.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let interner = FqdnInterner::new();
+/// let a = interner.intern(&fqdn!("github.com"));
+/// let b = interner.intern(&fqdn!("GitHub.com"));
+/// assert_eq![ a, b ];
+/// assert_eq![ *a, fqdn!("github.com") ];
+/// assert_eq![ interner.len(), 1 ];
+/// ```
+#[derive(Default)]
+pub struct FqdnInterner(Mutex<Pool>);
+
+#[derive(Default)]
+struct Pool {
+    names: HashSet<Arc<Fqdn>>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Statistics about the use of a [`FqdnInterner`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InternerStats {
+    /// Number of distinct FQDN currently stored
+    pub entries: usize,
+    /// Number of interned FQDN which were already stored
+    pub hits: u64,
+    /// Number of interned FQDN which were not already stored
+    pub misses: u64,
+}
+
+impl InternerStats {
+
+    /// Computes the ratio of interned FQDN which were already stored.
+    ///
+    /// If nothing was interned, the hit rate is 0.
+    #[inline]
+    pub fn hit_rate(&self) -> f64
+    {
+        match self.hits + self.misses {
+            0 => 0.,
+            total => self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl FqdnInterner {
+
+    /// Creates an empty interner.
+    #[inline]
+    pub fn new() -> Self { Self::default() }
+
+    /// Gets the process-wide interner.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let a = FqdnInterner::global().intern(&fqdn!("github.com"));
+    /// let b = std::thread::spawn(|| FqdnInterner::global().intern(&fqdn!("github.com"))).join().unwrap();
+    /// assert_eq![ a, b ];
+    /// ```
+    pub fn global() -> &'static FqdnInterner
+    {
+        static GLOBAL: OnceLock<FqdnInterner> = OnceLock::new();
+        GLOBAL.get_or_init(FqdnInterner::new)
+    }
+
+    /// Gets the handle of a FQDN, storing it if necessary.
+    pub fn intern(&self, fqdn: &Fqdn) -> Interned
+    {
+        let mut pool = self.pool();
+        match pool.names.get(fqdn) {
+            Some(interned) => {
+                let interned = Interned(interned.clone());
+                pool.hits += 1;
+                interned
+            }
+            None => {
+                let interned = Arc::<Fqdn>::from(fqdn);
+                pool.names.insert(interned.clone());
+                pool.misses += 1;
+                Interned(interned)
+            }
+        }
+    }
+
+    /// Gets the handle of a FQDN only if it is already stored.
+    ///
+    /// This does not change the statistics of the interner.
+    pub fn get(&self, fqdn: &Fqdn) -> Option<Interned>
+    {
+        self.pool().names.get(fqdn).cloned().map(Interned)
+    }
+
+    /// Returns the number of distinct FQDN currently stored.
+    #[inline]
+    pub fn len(&self) -> usize { self.pool().names.len() }
+
+    /// Checks if no FQDN is stored.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.pool().names.is_empty() }
+
+    /// Returns the statistics about the use of this interner.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let interner = FqdnInterner::new();
+    /// interner.intern(&fqdn!("github.com"));
+    /// interner.intern(&fqdn!("github.com"));
+    /// let stats = interner.stats();
+    /// assert_eq![ (stats.entries, stats.hits, stats.misses), (1, 1, 1) ];
+    /// assert_eq![ stats.hit_rate(), 0.5 ];
+    /// ```
+    pub fn stats(&self) -> InternerStats
+    {
+        let pool = self.pool();
+        InternerStats { entries: pool.names.len(), hits: pool.hits, misses: pool.misses }
+    }
+
+    /// Removes all the FQDN for which no handle is alive anymore.
+    ///
+    /// Returns the number of removed FQDN.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let interner = FqdnInterner::new();
+    /// let _github = interner.intern(&fqdn!("github.com"));
+    /// interner.intern(&fqdn!("crates.io"));
+    /// assert_eq![ interner.collect_garbage(), 1 ];
+    /// assert_eq![ interner.len(), 1 ];
+    /// ```
+    pub fn collect_garbage(&self) -> usize
+    {
+        let mut pool = self.pool();
+        let before = pool.names.len();
+        // the pool holds one reference, so the others are held by handles
+        pool.names.retain(|name| Arc::strong_count(name) > 1);
+        before - pool.names.len()
+    }
+
+    #[inline]
+    fn pool(&self) -> MutexGuard<'_, Pool>
+    {
+        // the pool remains consistent even if a thread panicked while holding the lock
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for FqdnInterner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        f.debug_tuple("FqdnInterner").field(&self.stats()).finish()
+    }
+}
+
+/// A handle to a FQDN stored in a [`FqdnInterner`].
+///
+/// Handles are compared (and hashed) by address: two handles coming from the same interner
+/// are equal if and only if they refer to the same FQDN. To compare handles from
+/// different interners, compare the dereferenced FQDN instead.
+#[derive(Clone)]
+pub struct Interned(Arc<Fqdn>);
+
+impl Interned {
+
+    /// Gets the shared FQDN behind this handle.
+    #[inline]
+    pub fn as_arc(&self) -> &Arc<Fqdn> { &self.0 }
+}
+
+impl ops::Deref for Interned {
+    type Target = Fqdn;
+    #[inline]
+    fn deref(&self) -> &Fqdn { &self.0 }
+}
+
+impl AsRef<Fqdn> for Interned {
+    #[inline]
+    fn as_ref(&self) -> &Fqdn { &self.0 }
+}
+
+impl From<Interned> for Arc<Fqdn> {
+    #[inline]
+    fn from(interned: Interned) -> Self { interned.0 }
+}
+
+impl PartialEq for Interned {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool { Arc::ptr_eq(&self.0, &other.0) }
+}
+
+impl Eq for Interned { }
+
+impl Hash for Interned {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) { Arc::as_ptr(&self.0).cast::<u8>().hash(state) }
+}
+
+impl fmt::Display for Interned {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.0.fmt(f) }
+}
+
+impl fmt::Debug for Interned {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.debug_tuple("Interned").field(&self.0).finish() }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn interning()
+    {
+        let interner = FqdnInterner::new();
+        let names = ["github.com.", "crates.io.", "GitHub.com.", "github.COM.", "docs.rs.", "crates.io."]
+            .map(|s| interner.intern(&s.parse::<FQDN>().unwrap()));
+
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), 3);
+        assert_eq!(names[0], names[2]);
+        assert_ne!(names[0], names[1]);
+        assert_eq!(interner.get(&fqdn!("docs.rs")), Some(names[4].clone()));
+        assert_eq!(interner.get(&fqdn!("www.docs.rs")), None);
+
+        let stats = interner.stats();
+        assert_eq!(stats, InternerStats { entries: 3, hits: 3, misses: 3 });
+        assert_eq!(stats.hit_rate(), 0.5);
+
+        // handles from another interner are not equal, but their FQDN are
+        let other = FqdnInterner::new().intern(&fqdn!("github.com"));
+        assert_ne!(names[0], other);
+        assert_eq!(*names[0], *other);
+    }
+
+    #[test]
+    fn garbage_collection()
+    {
+        let interner = FqdnInterner::new();
+        assert_eq!(interner.collect_garbage(), 0);
+
+        let github = interner.intern(&fqdn!("github.com"));
+        let crates = interner.intern(&fqdn!("crates.io"));
+        let copy = crates.clone();
+        drop(crates);
+        assert_eq!(interner.collect_garbage(), 0);
+
+        drop(copy);
+        assert_eq!(interner.collect_garbage(), 1);
+        assert_eq!(interner.get(&fqdn!("crates.io")), None);
+        assert_eq!(interner.intern(&fqdn!("github.com")), github);
+
+        drop(github);
+        assert_eq!(interner.collect_garbage(), 1);
+        assert!(interner.is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+use core::iter::FusedIterator;
+
+use crate::Fqdn;
+
+/// An iterator over the hierarchy of a FQDN, from the FQDN itself up to its TLD.
+///
+/// This struct is created by [`Fqdn::hierarchy`]. Walking it backward (see [`Iterator::rev`])
+/// goes from the TLD down to the FQDN itself, which is exactly what [`Ancestors`] does.
+#[derive(Debug, Clone)]
+pub struct Hierarchy<'a> {
+    // the complete byte sequence of the FQDN (including the trailing nul byte)
+    bytes: &'a [u8],
+    // offset of the next suffix to yield from the front
+    front: usize,
+    // offset of the last suffix yielded from the back
+    // (initially, the offset of the trailing nul byte)
+    back: usize,
+    // number of suffixes not yet yielded
+    remaining: usize,
+}
+
+impl<'a> Hierarchy<'a> {
+
+    #[inline]
+    pub(crate) fn new(fqdn: &'a Fqdn) -> Self
+    {
+        let bytes = fqdn.as_bytes();
+        Self::bounded(bytes, bytes.len() - 1)
+    }
+
+    /// Builds an iterator over all the suffixes starting before the `back` offset.
+    pub(crate) fn bounded(bytes: &'a [u8], back: usize) -> Self
+    {
+        let mut remaining = 0;
+        let mut pos = 0;
+        while pos < back {
+            pos += bytes[pos] as usize + 1;
+            remaining += 1;
+        }
+        Hierarchy { bytes, front: 0, back, remaining }
+    }
+
+    #[inline]
+    fn suffix_at(&self, pos: usize) -> &'a Fqdn
+    {
+        // it is safe because pos is always the start of a label
+        unsafe { Fqdn::from_bytes_unchecked(&self.bytes[pos..]) }
+    }
+}
+
+impl<'a> Iterator for Hierarchy<'a> {
+    type Item = &'a Fqdn;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        if self.remaining == 0 {
+            None
+        } else {
+            let current = self.front;
+            self.front += self.bytes[current] as usize + 1;
+            self.remaining -= 1;
+            Some(self.suffix_at(current))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { (self.remaining, Some(self.remaining)) }
+
+    #[inline]
+    fn count(self) -> usize { self.remaining }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> { self.next_back() }
+}
+
+impl DoubleEndedIterator for Hierarchy<'_> {
+
+    fn next_back(&mut self) -> Option<Self::Item>
+    {
+        if self.remaining == 0 {
+            None
+        } else {
+            // the labels are only chained forward, so look for the last one before the back bound
+            let mut pos = self.front;
+            loop {
+                let next = pos + self.bytes[pos] as usize + 1;
+                if next >= self.back { break; }
+                pos = next;
+            }
+            self.back = pos;
+            self.remaining -= 1;
+            Some(self.suffix_at(pos))
+        }
+    }
+}
+
+impl ExactSizeIterator for Hierarchy<'_> { }
+
+impl FusedIterator for Hierarchy<'_> { }
+
+
+/// An iterator over the ancestors of a FQDN, from its TLD down to the FQDN itself.
+///
+/// This struct is created by [`Fqdn::ancestors`] or [`Fqdn::ancestors_within`].
+/// It is the reverse of [`Hierarchy`].
+#[derive(Debug, Clone)]
+pub struct Ancestors<'a>(pub(crate) Hierarchy<'a>);
+
+impl<'a> Iterator for Ancestors<'a> {
+    type Item = &'a Fqdn;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> { self.0.next_back() }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+
+    #[inline]
+    fn count(self) -> usize { self.0.count() }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> { self.0.next() }
+}
+
+impl DoubleEndedIterator for Ancestors<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> { self.0.next() }
+}
+
+impl ExactSizeIterator for Ancestors<'_> { }
+
+impl FusedIterator for Ancestors<'_> { }
+
+
+/// An iterator over the labels of a FQDN, from the leftmost one to the TLD.
+///
+/// This struct is created by [`Fqdn::labels`].
+#[derive(Debug, Clone)]
+pub struct Labels<'a>(pub(crate) Hierarchy<'a>);
+
+#[inline]
+fn first_label(fqdn: &Fqdn) -> &str
+{
+    let bytes = fqdn.as_bytes();
+    // it is safe because a FQDN contains only ASCII characters
+    unsafe { core::str::from_utf8_unchecked(&bytes[1..=bytes[0] as usize]) }
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> { self.0.next().map(first_label) }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) { self.0.size_hint() }
+
+    #[inline]
+    fn count(self) -> usize { self.0.count() }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> { self.0.last().map(first_label) }
+}
+
+impl DoubleEndedIterator for Labels<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> { self.0.next_back().map(first_label) }
+}
+
+impl ExactSizeIterator for Labels<'_> { }
+
+impl FusedIterator for Labels<'_> { }
+
+
+/// The number of queries after which the remaining labels are added all at once (RFC 9156, section 2.3).
+pub const MAX_MINIMISE_COUNT: usize = 10;
+
+/// The number of queries which add only one label, before adding several labels at once (RFC 9156, section 2.3).
+pub const MINIMISE_ONE_LAB: usize = 4;
+
+/// An iterator over the names to query when resolving a FQDN with QNAME minimisation.
+///
+/// This struct is created by [`Fqdn::qname_minimisation_steps`].
+#[derive(Debug, Clone)]
+pub struct QnameMinimisation<'a> {
+    // the names below the known zone, down to the QNAME
+    hierarchy: Hierarchy<'a>,
+    // the number of queries already yielded (MINIMISE_COUNT)
+    count: usize,
+    // the number of leading labels of the QNAME which start with an underscore
+    underscores: usize,
+}
+
+impl<'a> QnameMinimisation<'a> {
+
+    pub(crate) fn new(qname: &'a Fqdn, zone: &Fqdn) -> Self
+    {
+        let bytes = qname.as_bytes();
+        let back = if qname == zone {
+            // only the QNAME itself
+            qname.first_label_length() + 1
+        } else if qname.is_subdomain_of(zone) {
+            // the zone is a suffix of the QNAME, so stop just before it
+            bytes.len() - zone.as_bytes().len()
+        } else {
+            // start from the top domain (or query the top domain itself)
+            (bytes.len() - 1).max(1)
+        };
+        QnameMinimisation {
+            hierarchy: Hierarchy::bounded(bytes, back),
+            count: 0,
+            underscores: qname.labels().take_while(|label| label.starts_with('_')).count(),
+        }
+    }
+}
+
+impl<'a> Iterator for QnameMinimisation<'a> {
+    type Item = &'a Fqdn;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let remaining = self.hierarchy.len();
+        if remaining == 0 {
+            return None;
+        }
+        let mut labels = if self.count < MINIMISE_ONE_LAB {
+            1
+        } else {
+            // spread the remaining labels over the remaining queries
+            (remaining / MAX_MINIMISE_COUNT.saturating_sub(self.count).max(1)).max(1)
+        };
+        if remaining - labels < self.underscores {
+            // the underscored labels are all added at once (e.g. `_25._tcp.mail.example.org`
+            // is queried right after `mail.example.org`)
+            labels = remaining;
+        }
+        self.count += 1;
+        self.hierarchy.nth_back(labels - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>)
+    {
+        let remaining = self.hierarchy.len();
+        (remaining.min(1), Some(remaining))
+    }
+}
+
+impl FusedIterator for QnameMinimisation<'_> { }
@@ -38,13 +38,66 @@
 //! If this feature is activated, then parsing or printing a FQDN strictly apply this rule. By default,
 //! the parsing behavior is more lenient (i.e. the trailing dot could miss).
 //!
+//! ### `domain-tld-cannot-be-all-numeric`
+//! The [RFC 3696](https://www.rfc-editor.org/rfc/rfc3696#section-2) specifies that a top level domain
+//! cannot be all-numeric, so that a FQDN as `1.2.3.4` is never mistaken for an IPv4 address.
+//! If this feature is activated, such FQDN are refused with [`Error::NumericTopLevelDomain`].
+//! This feature is not activated by `strict-rfc`.
+//! Anyway, [`Fqdn::looks_like_ip_literal`] detects them and [`Host`] parses them as IP addresses.
+//!
 //! ### `punycode`
 //! Allow the use of Unicode characters in FQDN. They are automatically encoded
 //! by using [Punycode](https://en.wikipedia.org/wiki/Punycode).
-//! When activated, the macro `fqdn!` implements the punycode, so do the trait `FromStr`
+//! When activated, the macro `fqdn!` implements the punycode, so do the trait `FromStr`.
+//! This feature requires `std`.
+//!
+//! ### `std` and `alloc`
+//! The feature `std` is activated by default. Without it, this crate is `no_std`:
+//! the borrowed [`Fqdn`] only relies on `core` and the owned [`FQDN`] (with the macros
+//! [`fqdn!`] and [`try_fqdn!`]) needs the `alloc` feature.
+//! The [`formats`] module, which reads and writes the lists of blocked domains of
+//! hosts files, dnsmasq, Unbound, AdBlock and RPZ, needs `std`.
+//! The [`ZoneContext`], which resolves the relative names of zone files (`$ORIGIN`, `@`,
+//! `$GENERATE`...), and the [`SearchList`], which expands short names as stub resolvers do
+//! (resolv.conf `search` and `ndots`), only need `alloc`.
+//!
+//! ### `domain`
+//! Provide zero-copy conversions with the names of the [`domain`](https://docs.rs/domain) crate,
+//! and implement the `domain` traits `ToLabelIter` and `ToName`, so that a FQDN could be
+//! directly used to build a DNS message.
+//! Since `domain` names are limited as the RFC specifies, this feature activates
+//! `domain-label-length-limited-to-63` and `domain-name-length-limited-to-255`.
+//!
+//! ### `hickory`
+//! Provide conversions and comparisons with [`hickory_proto::rr::Name`](https://docs.rs/hickory-proto/latest/hickory_proto/rr/domain/struct.Name.html),
+//! without going through the string representation.
+//! Since hickory names are limited as the RFC specifies, the conversion of a FQDN to a hickory
+//! name fails with [`Error::TooLongLabel`] or [`Error::TooLongDomainName`] if it is too long,
+//! which could only happen when `domain-label-length-limited-to-63` and
+//! `domain-name-length-limited-to-255` are not activated.
+//! This feature requires `std`.
+//!
+//! ### `url` and `http`
+//! Extract a FQDN from the host of an [`url::Url`](https://docs.rs/url) or of an
+//! [`http::uri::Authority`](https://docs.rs/http), as [`FQDN::from_host_header`] does for the
+//! value of a HTTP `Host` header. The IP addresses are rejected with [`Error::IpAddressLiteral`].
+//! The feature `url` also provides `FQDN::parse_url_host` which parses a [`Host`] exactly as browsers do.
+//! These features require `std`.
+//!
+//! ### `arbitrary` and `proptest`
+//! Generate FQDN which are always valid according to the activated features, either from raw
+//! data for fuzzing (through the [`arbitrary`](https://docs.rs/arbitrary) crate) or with
+//! shrinkable strategies for property testing (see the [`proptest`](mod@crate::proptest) module).
+//! The fuzz targets of this crate are in the `fuzz` directory (run them with `cargo fuzz`).
+//!
+//! ### `cli`
+//! Build the `fqdn` command-line tool, which validates, normalizes, converts (punycode, wire format,
+//! reverse pointers) and sorts the names given as arguments or read from the standard input
+//! (see `fqdn --help`). This feature activates `punycode`.
 //!
 //! ### `serde`
 //! Provide serialization and deseriatization for FQDN.
+//! See the [`serde`](mod@crate::serde) module for the available representations.
 //!
 //! # RFC related to FQDN
 //! The related RFC define some restrictions that are not activated by default.
@@ -57,10 +110,51 @@
 //!
 //! See above for more details.
 //!
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "std")]
+mod arena;
+#[cfg(feature = "alloc")]
+mod caa;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "alloc")]
+mod certificate;
 mod fqdnref;
+#[cfg(feature = "alloc")]
 mod fqdn;
 mod check;
+#[cfg(feature = "domain")]
+mod domain;
 mod eqcmp;
+#[cfg(feature = "std")]
+pub mod formats;
+#[cfg(feature = "hickory")]
+mod hickory;
+#[cfg(feature = "alloc")]
+mod host;
+#[cfg(feature = "alloc")]
+mod indexed;
+mod inline;
+#[cfg(feature = "std")]
+mod interner;
+mod iter;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+mod relation;
+#[cfg(feature = "alloc")]
+mod search;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "alloc")]
+mod zone;
+#[cfg(feature = "alloc")]
+mod zonetree;
 
 #[cfg(feature = "punycode")] mod punycode;
 
@@ -70,44 +164,148 @@ mod eqcmp;
 ///
 /// If the trailing dot is missing, it is automatically added.
 ///
+/// When all the elements are literals, the FQDN is checked at compile time.
+///
 /// # Examples
 /// ```
 /// use fqdn::fqdn;
 ///
 /// let fqdn = fqdn!("rust-lang", "github.io");
+///
+/// let io = "github.io";
+/// let fqdn = fqdn!("rust-lang", io);
 /// ```
 /// # Panics
 /// If one of the elements is not a valid symbol, the macro panics.
+/// If all the elements are literals, the compilation fails instead.
 /// ```should_panic
 /// use fqdn::fqdn;
 ///
-/// let s = fqdn!("w@w","fr"); // panics !!
+/// let fr = "fr";
+/// let s = fqdn!("w@w", fr); // panics !!
 /// ```
+/// ```compile_fail
+/// use fqdn::fqdn;
+///
+/// let s = fqdn!("w@w","fr"); // does not compile !!
+/// ```
+///
+/// To get an error instead of a panic, consider [`try_fqdn!`].
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! fqdn {
-    ($($args:expr),*) => {{
-        #[allow(unused_mut)]
-        let mut str = std::string::String::new();
+    () => {
+        $crate::FQDN::default()
+    };
+    ($($args:literal),+ $(,)?) => {{
+        const STR: &str = concat!($($args, "."),+);
+        const BYTES: [u8; $crate::__private::wire_len(STR)] = $crate::__private::encode(STR);
+        const FQDN: &$crate::Fqdn = $crate::Fqdn::from_static_bytes(&BYTES);
+        if STR.is_ascii() {
+            $crate::FQDN::from(FQDN)
+        } else {
+            // unicode labels should be encoded at runtime (punycode)
+            $crate::__private::parse_dotted(STR).unwrap()
+        }
+    }};
+    ($($args:expr),+ $(,)?) => {{
+        let mut str = $crate::__private::String::new();
         $( str += $args; str += "."; )*
-        if str.as_bytes().len() <= 1 {
-            $crate::FQDN::default()
+        $crate::__private::parse_dotted(&str).unwrap()
+    }};
+}
+
+/// Parses a string and creates a new FQDN, reporting an error if it is not valid.
+///
+/// The string could be given as a single expression or built as with [`format!`].
+/// As for [`fqdn!`], if the trailing dot is missing, it is automatically added.
+///
+/// # Examples
+/// ```
+/// use fqdn::*;
+///
+/// let repo = "rust-lang";
+/// assert_eq![ try_fqdn!("{repo}.github.io"), Ok(fqdn!("rust-lang.github.io")) ];
+/// assert_eq![ try_fqdn!("{}.github.io", "w@w"), Err(Error::InvalidLabelChar) ];
+///
+/// let host = String::from("crates.io");
+/// assert_eq![ try_fqdn!(host), Ok(fqdn!("crates.io")) ];
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! try_fqdn {
+    ($fmt:literal $(, $args:expr)* $(,)?) => {
+        $crate::__private::parse_dotted(&($crate::__private::format!($fmt $(, $args)*) + "."))
+    };
+    ($str:expr) => {
+        $crate::__private::parse_dotted(&($crate::__private::String::from(::core::convert::AsRef::<str>::as_ref(&$str)) + "."))
+    };
+}
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    use crate::{Error, FQDN};
+
+    #[cfg(feature = "alloc")]
+    pub use alloc::{format, string::String};
+    pub use crate::check::dotted_str_wire_len as wire_len;
+
+    // Converts a string with dot-terminated labels at compile time
+    pub const fn encode<const N: usize>(s: &str) -> [u8; N] {
+        match crate::check::encode_dotted_str(s) {
+            Ok(bytes) => bytes,
+            Err(e) => panic!("{}", e.as_str())
+        }
+    }
+
+    // Parses a string with dot-terminated labels at runtime
+    #[cfg(feature = "alloc")]
+    pub fn parse_dotted(str: &str) -> Result<FQDN, Error> {
+        if str.len() <= 1 {
+            Ok(FQDN::default())
         } else {
-            let penultimate = str.as_bytes().len() - 2;
-            // SAFETY: the length is checked just before
-            let str = match unsafe { str.as_bytes().get_unchecked(penultimate) } {
+            let penultimate = str.len() - 2;
+            let str = match str.as_bytes()[penultimate] {
                 b'.' => &str[..(penultimate+1)],
-                _ => &str
+                _ => str
             };
-            str.parse::<$crate::FQDN>().unwrap()
+            str.parse::<FQDN>()
         }
-    }}
+    }
 }
 
+#[cfg(feature = "alloc")]
 pub use crate::fqdn::FQDN;
 pub use fqdnref::Fqdn;
 pub use check::Error;
-
-#[cfg(test)]
+#[cfg(feature = "std")]
+pub use arena::{ArenaLabels, ArenaName, FqdnArena, NameId};
+#[cfg(feature = "alloc")]
+pub use caa::{CaaParseError, CaaProperty, CaaRecords};
+#[cfg(feature = "std")]
+pub use cache::{CacheLookup, Clock, FqdnCache, SystemClock};
+#[cfg(feature = "alloc")]
+pub use certificate::CertificateNameMatcher;
+#[cfg(feature = "alloc")]
+pub use indexed::{IndexedFqdn, IndexedLabels};
+#[cfg(feature = "alloc")]
+pub use host::Host;
+pub use inline::{InlineFqdn, INLINE_CAPACITY};
+#[cfg(feature = "std")]
+pub use interner::{FqdnInterner, Interned, InternerStats};
+pub use iter::{Ancestors, Hierarchy, Labels, QnameMinimisation, MAX_MINIMISE_COUNT, MINIMISE_ONE_LAB};
+pub use relation::Relation;
+#[cfg(feature = "alloc")]
+pub use search::SearchList;
+#[cfg(feature = "alloc")]
+pub use zone::{Generate, ZoneContext, ZoneError};
+#[cfg(feature = "alloc")]
+pub use zonetree::{ZoneLookup, ZoneTree, ZoneTreeError};
+#[cfg(feature = "domain")]
+pub use crate::domain::DomainLabels;
+
+#[cfg(all(test, feature = "alloc"))]
 mod tests {
     use std::collections::{BTreeSet, HashSet};
     use crate as fqdn;
@@ -152,6 +350,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn numeric_tld()
+    {
+        assert!(fqdn!("github.0x").looks_like_ip_literal());
+        assert!(fqdn!("1.2.3.0x4").looks_like_ip_literal());
+        assert!(!fqdn!("1.2.3.com").looks_like_ip_literal());
+        assert!(!FQDN::default().looks_like_ip_literal());
+
+        #[cfg(feature = "domain-tld-cannot-be-all-numeric")] {
+            assert_eq!("github.123.".parse::<FQDN>(), Err(Error::NumericTopLevelDomain));
+            assert_eq!("github.123.".parse::<InlineFqdn>(), Err(Error::NumericTopLevelDomain));
+            assert_eq!(Fqdn::from_bytes(b"\x06github\x03123\x00"), Err(Error::NumericTopLevelDomain));
+            assert_eq!(FQDN::from_vec(b"\x06github\x03123".to_vec()), Err(Error::NumericTopLevelDomain));
+            assert!(try_fqdn!("github.{}", 123).is_err());
+        }
+        #[cfg(not(feature = "domain-tld-cannot-be-all-numeric"))] {
+            assert!("github.123.".parse::<FQDN>().is_ok());
+            assert!(fqdn!("1.2.3.4").looks_like_ip_literal());
+        }
+
+        assert!("123.github.0x1.".parse::<FQDN>().is_ok());
+    }
+
     #[test]
     fn parsing_bytes()
     {
@@ -191,6 +412,45 @@ mod tests {
         assert_eq!( Ok(fqdn!("a.fr")), FQDN::from_vec(vec![1, b'a', 2, b'f', b'r', 0]) );
     }
 
+    #[test]
+    fn conversions()
+    {
+        use std::borrow::Cow;
+        use std::ffi::CString;
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let fqdn = fqdn!("crates.io");
+
+        let boxed: Box<Fqdn> = fqdn.clone().into();
+        assert_eq!(*boxed, fqdn);
+        assert_eq!(boxed.clone().into_fqdn(), fqdn);
+        assert_eq!(Box::<Fqdn>::from(fqdn.as_ref()), boxed);
+        assert!(Box::<Fqdn>::default().is_root());
+        assert!(<&Fqdn>::default().is_root());
+
+        let arc: Arc<Fqdn> = fqdn.clone().into();
+        let shared = arc.clone();
+        assert_eq!(*shared, fqdn);
+        assert_eq!(Arc::<Fqdn>::from(fqdn.as_ref()), arc);
+        let rc: Rc<Fqdn> = fqdn.clone().into();
+        assert_eq!(Rc::<Fqdn>::from(fqdn.as_ref()), rc);
+        assert_eq!(*rc, *arc);
+
+        let borrowed: Cow<Fqdn> = (&fqdn).into();
+        assert!(matches!(borrowed, Cow::Borrowed(_)));
+        let owned: Cow<Fqdn> = fqdn.clone().into();
+        assert_eq!(FQDN::from(owned), FQDN::from(borrowed));
+
+        assert_eq!(Vec::<u8>::from(fqdn.clone()), b"\x06crates\x02io\x00");
+        assert_eq!(CString::from(fqdn.clone()).as_c_str(), fqdn.as_c_str());
+        assert_eq!(FQDN::try_from(CString::from(fqdn.clone())), Ok(fqdn.clone()));
+        assert_eq!(FQDN::try_from(fqdn.to_string()), Ok(fqdn.clone()));
+        assert_eq!(FQDN::try_from("Crates.IO."), Ok(fqdn.clone()));
+        assert_eq!(String::from(fqdn.as_ref()), fqdn.to_string());
+        assert_eq!(String::from(fqdn.clone()), fqdn.to_string());
+    }
+
     #[test]
     fn check_bytes_label_with_lowercase()
     {
@@ -217,6 +477,48 @@ mod tests {
     }
 
 
+    #[test]
+    fn macros()
+    {
+        static GITHUB: &Fqdn = Fqdn::from_static_bytes(b"\x06github\x03com\x00");
+
+        assert!(fqdn!().is_root());
+        assert!(fqdn!(".").is_root());
+        assert!(fqdn!("").is_root());
+        assert_eq!(fqdn!("GitHub.COM"), *GITHUB);
+        assert_eq!(fqdn!("GitHub", "com."), *GITHUB);
+
+        let com = "com";
+        assert_eq!(fqdn!("github", com), *GITHUB);
+        assert_eq!(try_fqdn!("github.{com}"), Ok(GITHUB.to_owned()));
+        assert_eq!(try_fqdn!("github.{}.", com), Ok(GITHUB.to_owned()));
+        assert_eq!(try_fqdn!(com.to_string()), Ok(fqdn!("com")));
+        assert_eq!(try_fqdn!(".{com}"), Err(Error::EmptyLabel));
+        assert_eq!(try_fqdn!("github..{com}"), Err(Error::EmptyLabel));
+        assert_eq!(try_fqdn!("git#hub@{com}"), Err(Error::InvalidLabelChar));
+    }
+
+    #[test]
+    fn inline()
+    {
+        let inline = "GitHub.com.".parse::<InlineFqdn>().unwrap();
+        assert_eq!(inline, fqdn!("github.com"));
+        assert_eq!(inline.as_bytes(), b"\x06github\x03com\x00");
+        assert_eq!(FQDN::from(inline), inline);
+        assert_eq!(InlineFqdn::try_from(fqdn!("github.com").as_ref()), Ok(inline));
+        assert!(InlineFqdn::default().is_root());
+        assert_eq!(".".parse::<InlineFqdn>(), Ok(InlineFqdn::default()));
+
+        assert_eq!("github..com.".parse::<InlineFqdn>(), Err(fqdn::Error::EmptyLabel));
+        assert_eq!("git@ub.com.".parse::<InlineFqdn>(), Err(fqdn::Error::InvalidLabelChar));
+
+        const LENGTH_255: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.";
+        let inline = LENGTH_255.parse::<InlineFqdn>().unwrap();
+        assert_eq!(inline.as_bytes().len(), INLINE_CAPACITY);
+        assert_eq!(inline, LENGTH_255.parse::<FQDN>().unwrap());
+        assert_eq!(("a.".to_string() + LENGTH_255).parse::<InlineFqdn>(), Err(fqdn::Error::TooLongDomainName));
+    }
+
     #[test]
     fn depth()
     {
@@ -226,6 +528,74 @@ mod tests {
         assert_eq!("rust-lang.github.com.".parse::<FQDN>().map(|f| f.depth()), Ok(3));
     }
 
+    #[test]
+    fn iterators()
+    {
+        let fqdn = fqdn!("www.rust-lang.github.com");
+
+        let mut iter = fqdn.hierarchy();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back(), Some(fqdn!("com").as_ref()));
+        assert_eq!(iter.next(), Some(fqdn.as_ref()));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.next_back(), Some(fqdn!("github.com").as_ref()));
+        assert_eq!(iter.next_back(), Some(fqdn!("rust-lang.github.com").as_ref()));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+
+        assert!(fqdn.labels().rev().eq(["com", "github", "rust-lang", "www"]));
+        assert!(fqdn.ancestors().eq(fqdn.hierarchy().rev()));
+        assert_eq!(fqdn.ancestors_within(&FQDN::default()).len(), 4);
+        assert_eq!(fqdn.ancestors_within(&fqdn).collect::<Vec<_>>(), vec![fqdn.as_ref()]);
+        assert_eq!(fqdn.ancestors_within(&fqdn!("rust-lang.com")).len(), 0);
+        assert_eq!(fqdn.ancestors_within(&fqdn!("github.com")).next(), Some(fqdn!("github.com").as_ref()));
+
+        assert_eq!(fqdn.label(3), Some("com"));
+        assert!((0..=fqdn.depth()).all(|n| fqdn.suffix(n).map(|s| s.depth()) == Some(n)));
+
+        let root = FQDN::default();
+        assert_eq!(root.hierarchy().len(), 0);
+        assert_eq!(root.labels().next_back(), None);
+        assert_eq!(root.suffix(0), Some(root.as_ref()));
+        assert_eq!(root.suffix(1), None);
+    }
+
+    #[test]
+    fn qname_minimisation()
+    {
+        let steps = |qname: &Fqdn, zone: &Fqdn| qname.qname_minimisation_steps(zone).map(|f| f.depth()).collect::<Vec<_>>();
+
+        // one label at a time, then several ones
+        let deep = FQDN::from_vec((0..30).flat_map(|_| [1, b'a']).collect()).unwrap();
+        assert_eq!(steps(&deep, &FQDN::default()), [1, 2, 3, 4, 8, 12, 16, 20, 25, 30]);
+        assert_eq!(steps(&deep, deep.suffix(20).unwrap()), [21, 22, 23, 24, 25, 26, 27, 28, 29, 30]);
+        assert_eq!(steps(&deep, deep.suffix(22).unwrap()), [23, 24, 25, 26, 27, 28, 29, 30]);
+        assert!(steps(&deep, &FQDN::default()).len() <= MAX_MINIMISE_COUNT);
+
+        // known zone equal to the QNAME or unrelated
+        let fqdn = fqdn!("www.github.com");
+        assert_eq!(steps(&fqdn, &fqdn), [3]);
+        assert_eq!(steps(&fqdn, &fqdn!("crates.io")), [1, 2, 3]);
+        assert_eq!(steps(&FQDN::default(), &FQDN::default()), [0]);
+        assert_eq!(steps(&FQDN::default(), &fqdn!("com")), [0]);
+
+        // underscored labels
+        #[cfg(not(feature = "domain-name-without-special-chars"))]
+        {
+            let fqdn = fqdn!("_sip._tcp.example.com");
+            assert_eq!(steps(&fqdn, &fqdn!("com")), [2, 4]);
+            assert_eq!(steps(&fqdn, &fqdn!("_tcp.example.com")), [4]);
+            assert_eq!(steps(&fqdn!("_tcp.example.com"), &fqdn!("com")), [2, 3]);
+
+            let fqdn = fqdn!("_a._b._c.mail.example.org");
+            assert_eq!(steps(&fqdn, &fqdn!("org")), [2, 3, 6]);
+            assert_eq!(steps(&fqdn, &fqdn!("mail.example.org")), [6]);
+            assert_eq!(steps(&fqdn, &fqdn!("_c.mail.example.org")), [6]);
+            assert_eq!(steps(&fqdn, &FQDN::default()), [1, 2, 3, 6]);
+            assert_eq!(steps(&fqdn!("_a.b._c.example.org"), &fqdn!("org")), [2, 3, 4, 5]);
+        }
+    }
+
     #[test]
     fn subdomains()
     {
@@ -240,6 +610,59 @@ mod tests {
         assert_eq!( a, fqdn!("rust-lang","github","com") );
     }
 
+    #[test]
+    fn relations()
+    {
+        let a = fqdn!("rust-lang.github.com");
+        let b = fqdn!("www.GitHub.com");
+        let c = fqdn!("www.github.io");
+
+        assert_eq!(a.common_ancestor(&b), fqdn!("github.com").as_ref());
+        assert_eq!(a.common_ancestor(&c), FQDN::default().as_ref());
+        assert_eq!(a.common_ancestor(&FQDN::default()), FQDN::default().as_ref());
+        assert_eq!(fqdn!("a.b.c.d").common_ancestor(&fqdn!("x.b.y.d")), fqdn!("d").as_ref());
+        assert_eq!(fqdn!("a.b.c.d").common_ancestor(&fqdn!("c.d")), fqdn!("c.d").as_ref());
+
+        assert!(a.is_strict_subdomain_of(&fqdn!("com")));
+        assert!(!a.is_strict_subdomain_of(&a));
+        assert!(!a.is_strict_subdomain_of(&b));
+
+        assert_eq!(a.relation(&b), Relation::Sibling);
+        assert_eq!(a.relation(&c), Relation::Unrelated);
+        assert_eq!(FQDN::default().relation(&a), Relation::Ancestor(3));
+        assert_eq!(a.relation(&FQDN::default()), Relation::Descendant(3));
+        assert_eq!(a.distance(&c), 6);
+        assert_eq!(c.distance(c.parent().unwrap()), 1);
+
+        // labels of different lengths at the same depth, in both orders
+        let (long, short) = (fqdn!("aaaaaaaaaa.com"), fqdn!("b.com"));
+        assert_eq!(long.common_ancestor(&short), fqdn!("com").as_ref());
+        assert_eq!(short.common_ancestor(&long), fqdn!("com").as_ref());
+        assert_eq!(long.relation(&short), Relation::Sibling);
+        assert_eq!(short.relation(&long), Relation::Sibling);
+        assert_eq!(long.distance(&short), 2);
+        assert_eq!(fqdn!("x.aaaaaaaaaa").common_ancestor(&fqdn!("y.b")), FQDN::default().as_ref());
+        assert_eq!(fqdn!("y.b").common_ancestor(&fqdn!("x.aaaaaaaaaa")), FQDN::default().as_ref());
+    }
+
+    #[test]
+    fn canonical_order()
+    {
+        // the example of the RFC 4034 (without the special chars)
+        let sorted = [
+            fqdn!("example"), fqdn!("a.example"), fqdn!("yljkjljk.a.example"),
+            fqdn!("Z.a.example"), fqdn!("zABC.a.EXAMPLE"), fqdn!("z.example")
+        ];
+        let mut names = sorted.clone();
+        names.reverse();
+        names.sort_by(|a, b| a.canonical_cmp(b));
+        assert_eq!(names, sorted);
+
+        assert_eq!(FQDN::default().canonical_cmp(&fqdn!("com")), std::cmp::Ordering::Less);
+        assert_eq!(fqdn!("github.com").canonical_cmp(&fqdn!("GitHub.com")), std::cmp::Ordering::Equal);
+        assert_eq!(fqdn!("a.b").canonical_cmp(&fqdn!("b.a")), std::cmp::Ordering::Greater);
+    }
+
     #[test]
     fn equivalence()
     {
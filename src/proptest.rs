@@ -0,0 +1,216 @@
+//! Strategies generating valid FQDN for property testing with [proptest](https://docs.rs/proptest).
+//!
+//! The generated FQDN always conform to the activated features (length limits, allowed chars,
+//! hyphens...) and so do their shrunk values: a failing test is always reduced to a valid FQDN
+//! with fewer and shorter labels.
+//!
+//! # Example
+//! ```
+//! use fqdn::FQDN;
+//! use fqdn::proptest::{fqdns, Charset, FqdnParams};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     # /*
+//!     #[test]
+//!     # */
+//!     fn display_then_parse(fqdn in any::<FQDN>()) {
+//!         prop_assert_eq!(fqdn.to_string().parse::<FQDN>(), Ok(fqdn));
+//!     }
+//!
+//!     # /*
+//!     #[test]
+//!     # */
+//!     fn deep_service_names(fqdn in fqdns(FqdnParams { depth: 3..=8, label_len: 1..=10, charset: Charset::Underscore })) {
+//!         prop_assert!((3..=8).contains(&fqdn.depth()));
+//!     }
+//! }
+//! # display_then_parse();
+//! # deep_service_names();
+//! ```
+use core::ops::RangeInclusive;
+use alloc::format;
+use alloc::vec::Vec;
+
+use ::proptest::arbitrary::Arbitrary;
+use ::proptest::collection::vec;
+use ::proptest::strategy::{BoxedStrategy, Strategy};
+use ::proptest::string::string_regex;
+
+use crate::check::MAX_LABEL_LEN;
+use crate::FQDN;
+
+/// The characters used to generate the labels of a FQDN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Charset {
+    /// Letters, digits and hyphens, as specified by the RFC 1035.
+    #[default]
+    Ldh,
+    /// Letters, digits, hyphens and underscores (as in `_sip._tcp.example.org`).
+    ///
+    /// Underscores are not generated if the feature `domain-name-without-special-chars` is activated.
+    Underscore,
+    /// Internationalized labels, made of latin, greek, cyrillic or CJK letters,
+    /// encoded with Punycode (as in `xn--bcher-kva.example`).
+    #[cfg(feature = "punycode")]
+    Idn,
+}
+
+/// The parameters of the generation of FQDN.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FqdnParams {
+    /// The number of labels (the top domain is generated if zero is allowed).
+    pub depth: RangeInclusive<usize>,
+    /// The length of each label, limited to 63 if the feature `domain-label-length-limited-to-63` is activated.
+    ///
+    /// For internationalized labels, this is the number of chars before encoding.
+    pub label_len: RangeInclusive<usize>,
+    /// The characters used in labels.
+    pub charset: Charset,
+}
+
+impl Default for FqdnParams {
+    /// Generates from one to four LDH labels of at most 16 chars.
+    fn default() -> Self
+    {
+        Self { depth: 1..=4, label_len: 1..=16, charset: Charset::Ldh }
+    }
+}
+
+/// Builds a strategy generating FQDN according to the specified parameters.
+///
+/// Names exceeding the length limit of the feature `domain-name-length-limited-to-255`
+/// are filtered out, so the ranges of the parameters should keep them rare.
+///
+/// # Panics
+/// Panics if the range of label lengths does not contain any allowed length.
+pub fn fqdns(params: FqdnParams) -> BoxedStrategy<FQDN>
+{
+    let min = (*params.label_len.start()).max(1);
+    let max = (*params.label_len.end()).min(MAX_LABEL_LEN);
+    assert!(min <= max, "invalid label length range");
+
+    let class = match params.charset {
+        Charset::Ldh => "a-z0-9",
+        Charset::Underscore if cfg!(feature = "domain-name-without-special-chars") => "a-z0-9",
+        Charset::Underscore => "a-z0-9_",
+        #[cfg(feature = "punycode")]
+        Charset::Idn => return idn_fqdns(params.depth, min..=max),
+    };
+    let label = string_regex(&format!("[{class}-]{{{min},{max}}}"))
+        .expect("valid label regex")
+        .prop_map(|label| {
+            let mut label = label.into_bytes();
+            if cfg!(feature = "domain-label-cannot-start-or-end-with-hyphen") {
+                for c in [0, label.len() - 1] {
+                    if label[c] == b'-' {
+                        label[c] = b'a';
+                    }
+                }
+            }
+            label
+        });
+
+    vec(label, params.depth)
+        .prop_filter_map("the FQDN is too long", |labels| {
+            let mut bytes = Vec::with_capacity(labels.iter().map(|l| l.len() + 1).sum::<usize>() + 1);
+            for label in labels {
+                bytes.push(label.len() as u8);
+                bytes.extend(label);
+            }
+            #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+            if crate::check::has_numeric_tld(&bytes) {
+                *bytes.last_mut().unwrap() = b'a';
+            }
+            FQDN::from_vec(bytes).ok()
+        })
+        .boxed()
+}
+
+#[cfg(feature = "punycode")]
+fn idn_fqdns(depth: RangeInclusive<usize>, label_len: RangeInclusive<usize>) -> BoxedStrategy<FQDN>
+{
+    let label = string_regex(&format!("[a-z0-9à-öø-ÿα-ωа-я一-鿿]{{{},{}}}", label_len.start(), label_len.end()))
+        .expect("valid label regex");
+
+    vec(label, depth)
+        .prop_filter_map("the FQDN is too long once encoded", |labels| {
+            let mut fqdn = labels.join(".");
+            fqdn.push('.');
+            #[cfg(feature = "domain-tld-cannot-be-all-numeric")]
+            if labels.last().is_some_and(|tld| tld.bytes().all(|c| c.is_ascii_digit())) {
+                fqdn.insert(fqdn.len() - 1, 'a');
+            }
+            FQDN::punyencode(fqdn).ok()
+        })
+        .boxed()
+}
+
+impl Arbitrary for FQDN {
+    type Parameters = FqdnParams;
+    type Strategy = BoxedStrategy<FQDN>;
+
+    #[inline]
+    fn arbitrary_with(params: Self::Parameters) -> Self::Strategy { fqdns(params) }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+    use ::proptest::strategy::ValueTree;
+    use ::proptest::test_runner::TestRunner;
+    use crate as fqdn;
+    use fqdn::proptest::*;
+    use fqdn::*;
+
+    // the charsets whose label lengths are not changed by an encoding
+    const CHARSETS: &[Charset] = &[Charset::Ldh, Charset::Underscore];
+
+    proptest! {
+        #[test]
+        fn valid(fqdn in any::<FQDN>()) {
+            prop_assert_eq!(Fqdn::from_bytes(fqdn.as_bytes()), Ok(fqdn.as_ref()));
+            prop_assert_eq!(fqdn.to_string().parse::<FQDN>(), Ok(fqdn.clone()));
+            prop_assert!((1..=4).contains(&fqdn.depth()));
+            prop_assert!(fqdn.labels().all(|l| l.len() <= 16));
+        }
+
+        #[test]
+        fn params(charset in ::proptest::sample::select(CHARSETS), depth in 0..5_usize, len in 1..30_usize) {
+            let params = FqdnParams { depth: depth..=depth + 2, label_len: len..=len + 3, charset };
+            let fqdn = fqdns(params).new_tree(&mut TestRunner::deterministic()).unwrap().current();
+            prop_assert!((depth..=depth + 2).contains(&fqdn.depth()));
+            prop_assert!(fqdn.labels().all(|l| (len..=len + 3).contains(&l.len())));
+            if charset == Charset::Ldh {
+                prop_assert!(fqdn.labels().all(|l| l.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-')));
+            }
+        }
+    }
+
+    #[cfg(feature = "punycode")]
+    proptest! {
+        #[test]
+        fn idn(fqdn in fqdns(FqdnParams { depth: 1..=3, label_len: 1..=8, charset: Charset::Idn })) {
+            prop_assert!(fqdn.labels().all(|l| l.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-')));
+            prop_assert_eq!(FQDN::punyencode(fqdn.punydecode()), Ok(fqdn));
+        }
+    }
+
+    #[test]
+    fn shrinking()
+    {
+        // shrink a failing value and check that all the intermediate values are valid
+        let mut runner = TestRunner::deterministic();
+        let params = FqdnParams { depth: 2..=6, label_len: 5..=40, charset: Charset::Underscore };
+        let mut tree = fqdns(params).new_tree(&mut runner).unwrap();
+        let mut last = tree.current();
+        while tree.simplify() {
+            let fqdn = tree.current();
+            assert!(Fqdn::from_bytes(fqdn.as_bytes()).is_ok(), "{fqdn}");
+            last = fqdn;
+        }
+        assert_eq!(last.depth(), 2);
+        assert!(last.labels().all(|l| l.len() == 5), "{last}");
+    }
+}
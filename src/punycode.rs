@@ -21,24 +21,30 @@ impl FQDN {
                 .split('.')
                 .take_while(|s| !s.is_empty())
                 .map(|s| s.to_lowercase())
-                .try_fold(String::new(), |mut fqdn, label| {
+                .try_fold(Vec::new(), |mut fqdn, label| {
                     let puny = punycode::encode(&label)
                         .map_err(|_| Error::InvalidLabelChar)?;
-                    if puny.ends_with('-') {
-                        fqdn.push(label.len() as u8 as char);
-                        fqdn.push_str(&label);
+                    // the label is kept as is if it is only made of ascii chars
+                    let ascii = puny.ends_with('-');
+                    let len = if ascii { label.len() } else { 4 + puny.len() };
+                    if len > u8::MAX as usize {
+                        return Err(Error::TooLongLabel);
+                    }
+                    // the length byte is pushed as is (a char would be utf8-encoded above 127)
+                    fqdn.push(len as u8);
+                    if ascii {
+                        fqdn.extend_from_slice(label.as_bytes());
                     } else {
-                        fqdn.push((4 + puny.len()) as u8 as char);
-                        fqdn.push_str("xn--");
-                        fqdn.push_str(&puny);
+                        fqdn.extend_from_slice(b"xn--");
+                        fqdn.extend_from_slice(puny.as_bytes());
                     }
                     Ok(fqdn)
                 })
                 .and_then(|mut fqdn| {
-                    fqdn.push(0 as char);
-                    check_byte_sequence(fqdn.as_bytes())
+                    fqdn.push(0);
+                    check_byte_sequence(&fqdn)
                         .map(|_| unsafe { // SAFETY: just checked above
-                            Self(CString::from_vec_with_nul_unchecked(fqdn.into_bytes()))
+                            Self(CString::from_vec_with_nul_unchecked(fqdn))
                         })
                 })
         }
@@ -53,10 +59,10 @@ impl Fqdn {
         let mut fqdn = self.labels()
             .fold(String::with_capacity(self.as_bytes().len()),
                   |mut acc, label| {
-                      if label.starts_with("xn--") {
-                          acc.push_str(&punycode::decode(&label[4..]).unwrap());
-                      } else {
-                          acc.push_str(label);
+                      // a label which is not valid punycode is kept as is
+                      match label.strip_prefix("xn--").and_then(|puny| punycode::decode(puny).ok()) {
+                          Some(label) => acc.push_str(&label),
+                          None => acc.push_str(label)
                       }
                       acc.push('.');
                       acc
@@ -87,5 +93,13 @@ mod tests {
 
         #[cfg(feature = "domain-name-should-have-trailing-dot")]
         assert_eq!(fqdn.punydecode(), "www.académie-française.fr.".to_string());
+
+        // too long labels, once encoded
+        assert_eq!(FQDN::punyencode(format!("{}.", "é".repeat(300))), Err(Error::TooLongLabel));
+        assert!(FQDN::punyencode(format!("{}.", "é".repeat(5))).is_ok());
+
+        // invalid punycode
+        let fqdn = fqdn!("xn--99999999999.fr");
+        assert_eq!(fqdn.punydecode().trim_end_matches('.'), "xn--99999999999.fr");
     }
 }
\ No newline at end of file
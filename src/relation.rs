@@ -0,0 +1,175 @@
+use core::cmp::Ordering;
+
+use crate::Fqdn;
+
+/// The hierarchical relationship between two FQDN.
+///
+/// This is returned by [`Fqdn::relation`] and describes how the first
+/// FQDN is related to the second one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Relation {
+    /// Both FQDN are the same.
+    Equal,
+    /// The first FQDN is an ancestor of the second one.
+    ///
+    /// The distance is the number of labels between them (e.g. 1 for the immediate parent).
+    Ancestor(usize),
+    /// The first FQDN is a descendant of the second one.
+    ///
+    /// The distance is the number of labels between them (e.g. 1 for an immediate child).
+    Descendant(usize),
+    /// Both FQDN are distinct but share the same parent.
+    Sibling,
+    /// None of the previous relationships apply (they only share a farther ancestor, maybe the root).
+    Unrelated,
+}
+
+// Returns the offset of the suffix obtained after skipping the n first labels
+#[inline]
+fn skip_labels(bytes: &[u8], n: usize) -> usize
+{
+    (0..n).fold(0, |pos, _| pos + bytes[pos] as usize + 1)
+}
+
+impl Fqdn {
+
+    /// Gets the longest common suffix of two FQDN.
+    ///
+    /// Since every FQDN ends with the top domain, there is always a common ancestor
+    /// (at worst, the top domain itself).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let a = fqdn!("rust-lang.github.com.");
+    /// let b = fqdn!("www.github.com.");
+    /// assert_eq![ a.common_ancestor(&b), fqdn!("github.com.").as_ref() ];
+    /// assert_eq![ a.common_ancestor(&fqdn!("github.io.")), fqdn!(".").as_ref() ];
+    /// assert_eq![ a.common_ancestor(&fqdn!("github.com.")), fqdn!("github.com.").as_ref() ];
+    /// # }
+    /// ```
+    pub fn common_ancestor(&self, other: &Fqdn) -> &Fqdn
+    {
+        let (depth, other_depth) = (self.depth(), other.depth());
+        let common_depth = depth.min(other_depth);
+
+        // align both FQDN on the same depth
+        let bytes = self.as_bytes();
+        let mut pos = skip_labels(bytes, depth - common_depth);
+        let other_bytes = other.as_bytes();
+        let mut other_pos = skip_labels(other_bytes, other_depth - common_depth);
+
+        // then, look for the start of the last run of equal labels
+        let mut common = bytes.len() - 1;
+        let mut matching = false;
+        while bytes[pos] != 0 {
+            let len = bytes[pos] as usize + 1;
+            // the lengths are compared first, so that the other label is never sliced beyond its end
+            if bytes[pos] == other_bytes[other_pos] && bytes[pos..pos + len] == other_bytes[other_pos..other_pos + len] {
+                if !matching {
+                    common = pos;
+                    matching = true;
+                }
+            } else {
+                matching = false;
+            }
+            pos += len;
+            other_pos += other_bytes[other_pos] as usize + 1;
+        }
+        if !matching {
+            common = bytes.len() - 1;
+        }
+        // it is safe since common is the start of a label (or the trailing nul byte)
+        unsafe { Fqdn::from_bytes_unchecked(&bytes[common..]) }
+    }
+
+    /// Checks if this domain is a descendant of another one, but not this one.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// assert![ fqdn!("www.rust-lang.github.com").is_strict_subdomain_of(&fqdn!("github.com.")) ];
+    /// assert![ ! fqdn!("github.com.").is_strict_subdomain_of(&fqdn!("github.com.")) ];
+    /// # }
+    /// ```
+    #[inline]
+    pub fn is_strict_subdomain_of(&self, parent: &Fqdn) -> bool
+    {
+        self.as_bytes().len() > parent.as_bytes().len() && self.is_subdomain_of(parent)
+    }
+
+    /// Computes the number of label hops between two FQDN, going through their common ancestor.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let a = fqdn!("rust-lang.github.com.");
+    /// assert_eq![ a.distance(&a), 0 ];
+    /// assert_eq![ a.distance(&fqdn!("com.")), 2 ];
+    /// assert_eq![ a.distance(&fqdn!("www.github.com.")), 2 ];
+    /// assert_eq![ a.distance(&fqdn!("crates.io.")), 5 ];
+    /// # }
+    /// ```
+    pub fn distance(&self, other: &Fqdn) -> usize
+    {
+        let common = self.common_ancestor(other).depth();
+        self.depth() + other.depth() - 2 * common
+    }
+
+    /// Compares two FQDN in the canonical order of DNS names.
+    ///
+    /// As specified by [RFC 4034](https://www.rfc-editor.org/rfc/rfc4034#section-6.1),
+    /// the labels are compared from the rightmost one, so that a domain is sorted just before
+    /// all its subdomains (as in a zone file or a `NSEC` chain).
+    /// Notice that it differs from the order of [`Ord`], which compares the byte sequences.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let mut names = vec![fqdn!("z.example."), fqdn!("a.example."), fqdn!("example."), fqdn!("yljkjljk.a.example.")];
+    /// names.sort_by(|a, b| a.canonical_cmp(b));
+    /// assert_eq![ names, [fqdn!("example."), fqdn!("a.example."), fqdn!("yljkjljk.a.example."), fqdn!("z.example.")] ];
+    /// # }
+    /// ```
+    #[inline]
+    pub fn canonical_cmp(&self, other: &Fqdn) -> Ordering
+    {
+        self.labels().rev().cmp(other.labels().rev())
+    }
+
+    /// Determines how this FQDN is related to another one.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// # #[cfg(feature = "alloc")] {
+    /// let a = fqdn!("rust-lang.github.com.");
+    /// assert_eq![ a.relation(&a), Relation::Equal ];
+    /// assert_eq![ a.relation(&fqdn!("com.")), Relation::Descendant(2) ];
+    /// assert_eq![ fqdn!("com.").relation(&a), Relation::Ancestor(2) ];
+    /// assert_eq![ a.relation(&fqdn!("www.github.com.")), Relation::Sibling ];
+    /// assert_eq![ a.relation(&fqdn!("www.github.io.")), Relation::Unrelated ];
+    /// # }
+    /// ```
+    pub fn relation(&self, other: &Fqdn) -> Relation
+    {
+        let common = self.common_ancestor(other);
+        let (depth, other_depth, common_depth) = (self.depth(), other.depth(), common.depth());
+
+        if depth == common_depth && other_depth == common_depth {
+            Relation::Equal
+        } else if depth == common_depth {
+            Relation::Ancestor(other_depth - common_depth)
+        } else if other_depth == common_depth {
+            Relation::Descendant(depth - common_depth)
+        } else if depth == common_depth + 1 && other_depth == common_depth + 1 {
+            Relation::Sibling
+        } else {
+            Relation::Unrelated
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use alloc::vec::Vec;
+
+use crate::{try_fqdn, Error, Fqdn, FQDN};
+
+// the maximum value of `ndots` accepted by the stub resolvers
+const MAX_NDOTS: u8 = 15;
+
+/// The search list of a stub resolver, which expands short names to FQDN.
+///
+/// A name ending with a dot is absolute and is the only candidate.
+/// Otherwise, a name with at least `ndots` dots is first tried as is, then completed by each
+/// domain of the search list. A name with fewer dots is first completed by the search list
+/// and is finally tried as is.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let search = SearchList::from_resolv_conf("search corp.example.com example.com\noptions ndots:2\n");
+///
+/// let candidates = search.candidates("api").collect::<Vec<_>>();
+/// assert_eq![ candidates, vec![fqdn!("api.corp.example.com"), fqdn!("api.example.com"), fqdn!("api")] ];
+///
+/// let candidates = search.candidates("www.github.com").collect::<Vec<_>>();
+/// assert_eq![ candidates, vec![fqdn!("www.github.com"), fqdn!("www.github.com.corp.example.com"), fqdn!("www.github.com.example.com")] ];
+///
+/// let candidates = search.candidates("api.").collect::<Vec<_>>();
+/// assert_eq![ candidates, vec![fqdn!("api")] ];
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SearchList {
+    search: Vec<FQDN>,
+    ndots: u8,
+}
+
+impl Default for SearchList {
+    /// Builds an empty search list with `ndots:1`, as a resolver without configuration does.
+    #[inline]
+    fn default() -> Self { Self { search: Vec::new(), ndots: 1 } }
+}
+
+impl SearchList {
+
+    /// Builds a search list from its domains (in the order they should be tried) and its `ndots` option.
+    ///
+    /// As for resolv.conf, `ndots` is capped to 15.
+    #[inline]
+    pub fn new(search: Vec<FQDN>, ndots: u8) -> Self
+    {
+        Self { search, ndots: ndots.min(MAX_NDOTS) }
+    }
+
+    /// Builds the search list that Kubernetes sets up for the pods of a namespace.
+    ///
+    /// The search list is `<namespace>.svc.<cluster domain>`, `svc.<cluster domain>` and
+    /// `<cluster domain>`, with `ndots:5`. The error is returned if the namespace is not a valid label.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let search = SearchList::kubernetes("default", &fqdn!("cluster.local"))?;
+    /// assert_eq![ search.candidates("db").next(), Some(fqdn!("db.default.svc.cluster.local")) ];
+    /// assert_eq![ search.candidates("db.prod").nth(1), Some(fqdn!("db.prod.svc.cluster.local")) ];
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn kubernetes(namespace: &str, cluster_domain: &Fqdn) -> Result<Self, Error>
+    {
+        let svc = append(&try_fqdn!("svc")?, cluster_domain)?;
+        let namespace = try_fqdn!(namespace)?;
+        if namespace.depth() != 1 {
+            return Err(Error::InvalidLabelChar);
+        }
+        let search = alloc::vec![append(&namespace, &svc)?, svc, FQDN::from(cluster_domain)];
+        Ok(Self { search, ndots: 5 })
+    }
+
+    /// Parses the `search`, `domain` and `options ndots:n` directives of a resolv.conf file.
+    ///
+    /// As for the stub resolvers, `search` and `domain` override each other (the last one wins),
+    /// the other directives and options are ignored and so are the invalid domains of the search list.
+    pub fn from_resolv_conf(conf: &str) -> Self
+    {
+        let mut list = Self::default();
+        for line in conf.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("domain") => list.search = tokens.next().and_then(|d| try_fqdn!(d).ok()).into_iter().collect(),
+                Some("search") => list.search = tokens.filter_map(|d| try_fqdn!(d).ok()).collect(),
+                Some("options") => {
+                    for ndots in tokens.filter_map(|option| option.strip_prefix("ndots:")) {
+                        if let Ok(ndots) = ndots.parse::<u32>() {
+                            list.ndots = ndots.min(MAX_NDOTS as u32) as u8;
+                        }
+                    }
+                }
+                // comments (`#` or `;`) and other directives
+                _ => {}
+            }
+        }
+        list
+    }
+
+    /// Reads the search list of the system from `/etc/resolv.conf`.
+    #[cfg(feature = "std")]
+    pub fn system() -> std::io::Result<Self>
+    {
+        std::fs::read_to_string("/etc/resolv.conf").map(|conf| Self::from_resolv_conf(&conf))
+    }
+
+    /// Gets the domains of the search list.
+    #[inline]
+    pub fn search(&self) -> &[FQDN] { &self.search }
+
+    /// Gets the minimal number of dots for a name to be first tried as is.
+    #[inline]
+    pub fn ndots(&self) -> u8 { self.ndots }
+
+    /// Lists the FQDN a stub resolver would query for a name, in order.
+    ///
+    /// An invalid name has no candidate, and the candidates which would be too long
+    /// (according to the activated features) are skipped. Duplicated candidates are only listed once.
+    pub fn candidates(&self, name: &str) -> impl Iterator<Item = FQDN>
+    {
+        let mut candidates = Vec::new();
+        let Ok(fqdn) = try_fqdn!(name) else { return candidates.into_iter() };
+
+        if name.ends_with('.') || fqdn.is_root() {
+            candidates.push(fqdn);
+            return candidates.into_iter();
+        }
+
+        let first = name.bytes().filter(|&c| c == b'.').count() >= self.ndots as usize;
+        if first {
+            candidates.push(fqdn.clone());
+        }
+        for domain in &self.search {
+            if let Ok(candidate) = append(&fqdn, domain) {
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        if !first && !candidates.contains(&fqdn) {
+            candidates.push(fqdn);
+        }
+        candidates.into_iter()
+    }
+}
+
+// Concatenates the labels of a name and of a domain
+fn append(name: &Fqdn, domain: &Fqdn) -> Result<FQDN, Error>
+{
+    let name = name.as_bytes();
+    let mut bytes = Vec::with_capacity(name.len() + domain.as_bytes().len());
+    bytes.extend_from_slice(&name[..name.len() - 1]);
+    bytes.extend_from_slice(domain.as_bytes());
+    FQDN::from_vec(bytes)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    fn candidates(search: &SearchList, name: &str) -> Vec<String>
+    {
+        search.candidates(name).map(|fqdn| fqdn.to_string().trim_end_matches('.').to_string()).collect()
+    }
+
+    #[test]
+    fn resolv_conf()
+    {
+        let search = SearchList::from_resolv_conf("\
+            # generated by NetworkManager\n\
+            nameserver 192.0.2.53\n\
+            domain example.org\n\
+            search corp.example.com. example.com bad..domain\n\
+            ; options attempts:2\n\
+            options timeout:1 ndots:3 rotate\n");
+        assert_eq!(search.search(), [fqdn!("corp.example.com"), fqdn!("example.com")]);
+        assert_eq!(search.ndots(), 3);
+
+        let search = SearchList::from_resolv_conf("search example.com\ndomain example.org\noptions ndots:42\n");
+        assert_eq!(search.search(), [fqdn!("example.org")]);
+        assert_eq!(search.ndots(), 15);
+
+        assert_eq!(SearchList::from_resolv_conf(""), SearchList::default());
+        assert_eq!(SearchList::from_resolv_conf("options ndots:x"), SearchList::default());
+    }
+
+    #[test]
+    fn expansion()
+    {
+        let search = SearchList::new(vec![fqdn!("corp.example.com"), fqdn!("example.com")], 1);
+        assert_eq!(candidates(&search, "api"), ["api.corp.example.com", "api.example.com", "api"]);
+        assert_eq!(candidates(&search, "db.internal"), ["db.internal", "db.internal.corp.example.com", "db.internal.example.com"]);
+        assert_eq!(candidates(&search, "api.example.com."), ["api.example.com"]);
+        assert_eq!(candidates(&search, "Api"), ["api.corp.example.com", "api.example.com", "api"]);
+        assert_eq!(candidates(&search, "a..b"), Vec::<String>::new());
+        assert_eq!(candidates(&search, ""), [""]);
+
+        // ndots:0 tries the name first, even without any dot
+        let search = SearchList::new(vec![fqdn!("example.com"), FQDN::default()], 0);
+        assert_eq!(candidates(&search, "api"), ["api", "api.example.com"]);
+
+        // no search list
+        assert_eq!(candidates(&SearchList::default(), "api"), ["api"]);
+    }
+
+    #[test]
+    fn kubernetes()
+    {
+        let search = SearchList::kubernetes("prod", &fqdn!("cluster.local")).unwrap();
+        assert_eq!(search.ndots(), 5);
+        assert_eq!(candidates(&search, "db"), [
+            "db.prod.svc.cluster.local", "db.svc.cluster.local", "db.cluster.local", "db"
+        ]);
+        assert_eq!(candidates(&search, "a.b.c.d.e.f"), [
+            "a.b.c.d.e.f", "a.b.c.d.e.f.prod.svc.cluster.local", "a.b.c.d.e.f.svc.cluster.local", "a.b.c.d.e.f.cluster.local"
+        ]);
+        assert_eq!(SearchList::kubernetes("a.b", &fqdn!("cluster.local")), Err(Error::InvalidLabelChar));
+        assert!(SearchList::kubernetes("", &fqdn!("cluster.local")).is_err());
+    }
+}
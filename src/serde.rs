@@ -0,0 +1,437 @@
+//! Serialization and deserialization of FQDN.
+//!
+//! By default, a FQDN is serialized as its human-readable string (e.g. `"github.com"`)
+//! for human-readable formats (such as JSON) and as its byte sequence
+//! (e.g. `b"\x06github\x03com\x00"`) for binary formats (such as postcard).
+//! In the latter case, a borrowed [`&Fqdn`](crate::Fqdn) could be deserialized without any copy.
+//!
+//! The submodules of this module allow to force another representation or validation policy
+//! through the `with` attribute of serde. They could be used with any type which could be
+//! built from a [`FQDN`](crate::FQDN) (such as `FQDN`, `Box<Fqdn>` or `Arc<Fqdn>`).
+//!
+//! # Example
+//! ```
+//! use fqdn::{fqdn, FQDN};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Debug)]
+//! struct Zone {
+//!     #[serde(with = "fqdn::serde::labels")]
+//!     origin: FQDN,
+//!     #[serde(with = "fqdn::serde::wire")]
+//!     primary: FQDN,
+//! }
+//!
+//! let zone = Zone { origin: fqdn!("github.com"), primary: fqdn!("ns.github.com") };
+//! let json = serde_json::to_string(&zone).unwrap();
+//! assert_eq![ json, r#"{"origin":["github","com"],"primary":[2,110,115,6,103,105,116,104,117,98,3,99,111,109,0]}"# ];
+//! assert_eq![ serde_json::from_str::<Zone>(&json).unwrap(), zone ];
+//! ```
+use core::fmt;
+
+use ::serde::de::{self, Visitor};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Fqdn;
+#[cfg(feature = "alloc")]
+use crate::{Error, FQDN};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+impl Serialize for Fqdn {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            text::serialize(&self, serializer)
+        } else {
+            wire::serialize(&self, serializer)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Serialize for FQDN {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for FQDN {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            text::deserialize(deserializer)
+        } else {
+            wire::deserialize(deserializer)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de> Deserialize<'de> for Box<Fqdn> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        FQDN::deserialize(deserializer).map(FQDN::into_boxed_fqdn)
+    }
+}
+
+/// Deserializes a borrowed FQDN from its byte sequence, without any copy.
+///
+/// This is only possible for binary formats which store the byte sequence as is.
+impl<'de: 'a, 'a> Deserialize<'de> for &'a Fqdn {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(BorrowedVisitor)
+    }
+}
+
+struct BorrowedVisitor;
+
+impl<'de> Visitor<'de> for BorrowedVisitor {
+    type Value = &'de Fqdn;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a borrowed FQDN byte sequence")
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        Fqdn::from_bytes(v).map_err(E::custom)
+    }
+}
+
+// Builds a FQDN from a string
+#[cfg(feature = "alloc")]
+struct TextVisitor;
+
+#[cfg(feature = "alloc")]
+impl Visitor<'_> for TextVisitor {
+    type Value = FQDN;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a FQDN string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+// Builds a FQDN from its byte sequence
+#[cfg(feature = "alloc")]
+struct WireVisitor;
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for WireVisitor {
+    type Value = FQDN;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a FQDN byte sequence")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        FQDN::from_vec(v.to_vec()).map_err(E::custom)
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        FQDN::from_vec(v).map_err(E::custom)
+    }
+
+    // some formats (such as JSON) represent bytes as a list of integers
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        self.visit_byte_buf(bytes)
+    }
+}
+
+// Builds a FQDN from a list of labels
+#[cfg(feature = "alloc")]
+struct LabelsVisitor;
+
+#[cfg(feature = "alloc")]
+impl<'de> Visitor<'de> for LabelsVisitor {
+    type Value = FQDN;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of FQDN labels")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut fqdn = String::new();
+        while let Some(label) = seq.next_element::<String>()? {
+            // each item should be exactly one label
+            if label.is_empty() {
+                return Err(de::Error::custom(Error::EmptyLabel));
+            }
+            if label.contains('.') {
+                return Err(de::Error::custom(Error::InvalidLabelChar));
+            }
+            fqdn.push_str(&label);
+            fqdn.push('.');
+        }
+        crate::__private::parse_dotted(&fqdn).map_err(de::Error::custom)
+    }
+}
+
+/// Serializes a FQDN as its human-readable string (e.g. `"github.com"`),
+/// whatever the format.
+pub mod text {
+    use super::*;
+
+    /// Serializes a FQDN as its human-readable string.
+    #[inline]
+    pub fn serialize<S: Serializer, T: AsRef<Fqdn> + ?Sized>(fqdn: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(fqdn.as_ref())
+    }
+
+    /// Deserializes a FQDN from its human-readable string.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<FQDN>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_str(TextVisitor).map(T::from)
+    }
+}
+
+/// Serializes a FQDN as its byte sequence (e.g. `b"\x06github\x03com\x00"`),
+/// whatever the format.
+pub mod wire {
+    use super::*;
+
+    /// Serializes a FQDN as its byte sequence.
+    #[inline]
+    pub fn serialize<S: Serializer, T: AsRef<Fqdn> + ?Sized>(fqdn: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(fqdn.as_ref().as_bytes())
+    }
+
+    /// Deserializes a FQDN from its byte sequence.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<FQDN>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_bytes(WireVisitor).map(T::from)
+    }
+}
+
+/// Serializes a FQDN as the list of its labels (e.g. `["github", "com"]`),
+/// whatever the format.
+pub mod labels {
+    use super::*;
+
+    /// Serializes a FQDN as the list of its labels.
+    #[inline]
+    pub fn serialize<S: Serializer, T: AsRef<Fqdn> + ?Sized>(fqdn: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(fqdn.as_ref().labels())
+    }
+
+    /// Deserializes a FQDN from the list of its labels.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<FQDN>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_seq(LabelsVisitor).map(T::from)
+    }
+}
+
+/// Applies all the rules of the RFC when deserializing, whatever the activated features.
+///
+/// The representation is the default one (a string for human-readable formats
+/// and the byte sequence otherwise).
+///
+/// # Example
+/// ```
+/// use fqdn::FQDN;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Host(#[serde(with = "fqdn::serde::strict")] FQDN);
+///
+/// assert![ serde_json::from_str::<Host>(r#""github.com.""#).is_ok() ];
+/// assert![ serde_json::from_str::<Host>(r#""git_hub.com.""#).is_err() ];
+/// ```
+pub mod strict {
+    use super::*;
+
+    /// Serializes a FQDN with its default representation.
+    #[inline]
+    pub fn serialize<S: Serializer, T: AsRef<Fqdn> + ?Sized>(fqdn: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        fqdn.as_ref().serialize(serializer)
+    }
+
+    /// Deserializes a FQDN with its default representation and checks all the rules of the RFC.
+    #[cfg(feature = "alloc")]
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<FQDN>>(deserializer: D) -> Result<T, D::Error> {
+        let fqdn = FQDN::deserialize(deserializer)?;
+        fqdn.check_strict_rfc()
+            .map(|_| fqdn.into())
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Accepts any representation of a FQDN when deserializing.
+///
+/// The FQDN could be given as a string (with or without its trailing dot,
+/// whatever the activated features), as a byte sequence or as a list of labels.
+/// Notice that the rules of the activated features still apply, since they are
+/// invariants of [`FQDN`](crate::FQDN).
+///
+/// Since formats which are not self-describing can only provide the byte sequence,
+/// it is the expected representation for them.
+///
+/// # Example
+/// ```
+/// use fqdn::{fqdn, FQDN};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Host(#[serde(with = "fqdn::serde::relaxed")] FQDN);
+///
+/// let hosts: Vec<Host> = serde_json::from_str(r#"["github.com", "github.com.", ["github", "com"]]"#).unwrap();
+/// assert![ hosts.iter().all(|h| h.0 == fqdn!("github.com")) ];
+/// ```
+pub mod relaxed {
+    use super::*;
+
+    /// Serializes a FQDN with its default representation.
+    #[inline]
+    pub fn serialize<S: Serializer, T: AsRef<Fqdn> + ?Sized>(fqdn: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        fqdn.as_ref().serialize(serializer)
+    }
+
+    /// Deserializes a FQDN from any of its representations.
+    #[cfg(feature = "alloc")]
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<FQDN>>(deserializer: D) -> Result<T, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(RelaxedVisitor).map(T::from)
+        } else {
+            deserializer.deserialize_bytes(RelaxedVisitor).map(T::from)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    struct RelaxedVisitor;
+
+    #[cfg(feature = "alloc")]
+    impl<'de> Visitor<'de> for RelaxedVisitor {
+        type Value = FQDN;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a FQDN string, byte sequence or list of labels")
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            // the trailing dot is added, so the macro rules apply
+            crate::__private::parse_dotted(&(String::from(v) + ".")).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            WireVisitor.visit_bytes(v)
+        }
+
+        fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            WireVisitor.visit_byte_buf(v)
+        }
+
+        fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+            LabelsVisitor.visit_seq(seq)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::sync::Arc;
+    use crate as fqdn;
+    use fqdn::*;
+    use ::serde::de::{self, value::BorrowedStrDeserializer};
+    use ::serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Representations {
+        default: FQDN,
+        #[serde(with = "fqdn::serde::text")]
+        text: FQDN,
+        #[serde(with = "fqdn::serde::wire")]
+        wire: Box<Fqdn>,
+        #[serde(with = "fqdn::serde::labels")]
+        labels: Arc<Fqdn>,
+    }
+
+    fn representations() -> Representations
+    {
+        Representations {
+            default: fqdn!("github.com"),
+            text: fqdn!("github.com"),
+            wire: fqdn!("github.com").into(),
+            labels: fqdn!("github.com").into(),
+        }
+    }
+
+    #[test]
+    fn human_readable()
+    {
+        let json = serde_json::to_string(&representations()).unwrap();
+        let text = serde_json::to_string(&fqdn!("github.com").to_string()).unwrap();
+        assert_eq!(json, format!(r#"{{"default":{text},"text":{text},"wire":[6,103,105,116,104,117,98,3,99,111,109,0],"labels":["github","com"]}}"#));
+        assert_eq!(serde_json::from_str::<Representations>(&json).unwrap(), representations());
+
+        assert!(serde_json::from_str::<FQDN>(r#""git@hub.com.""#).is_err());
+        assert!(serde_json::from_str::<Box<Fqdn>>(r#""github.com.""#).is_ok());
+        // a string is never read as a byte sequence
+        let text = BorrowedStrDeserializer::<de::value::Error>::new("\x03com\x00");
+        assert!(<&Fqdn>::deserialize(text).is_err());
+
+        assert_eq!(serde_json::to_string(&FQDN::default()).unwrap(), r#"".""#);
+        assert_eq!(serde_json::from_str::<FQDN>(r#"".""#).unwrap(), FQDN::default());
+    }
+
+    #[test]
+    fn binary()
+    {
+        let bytes = postcard::to_stdvec(&representations()).unwrap();
+        assert_eq!(postcard::from_bytes::<Representations>(&bytes).unwrap(), representations());
+
+        let bytes = postcard::to_stdvec(&fqdn!("GitHub.com")).unwrap();
+        assert_eq!(bytes, b"\x0c\x06github\x03com\x00");
+        assert_eq!(postcard::from_bytes::<&Fqdn>(&bytes).unwrap(), fqdn!("github.com").as_ref());
+        assert_eq!(postcard::from_bytes::<FQDN>(&bytes).unwrap(), fqdn!("github.com"));
+
+        // borrowing requires the exact byte sequence
+        assert!(postcard::from_bytes::<&Fqdn>(b"\x0c\x06GitHub\x03com\x00").is_err());
+        assert_eq!(postcard::from_bytes::<FQDN>(b"\x0c\x06GitHub\x03com\x00").unwrap(), fqdn!("github.com"));
+        assert!(postcard::from_bytes::<FQDN>(b"\x0c\x07github\x03com\x00").is_err());
+    }
+
+    #[test]
+    fn labels()
+    {
+        #[derive(Deserialize)]
+        struct Labels(#[serde(with = "fqdn::serde::labels")] FQDN);
+
+        assert_eq!(serde_json::from_str::<Labels>("[]").unwrap().0, FQDN::default());
+        assert!(serde_json::from_str::<Labels>(r#"["github", ""]"#).is_err());
+        assert!(serde_json::from_str::<Labels>(r#"["github.com"]"#).is_err());
+        assert!(serde_json::from_str::<Labels>(r#"["git@hub", "com"]"#).is_err());
+    }
+
+    #[test]
+    fn policies()
+    {
+        #[derive(Serialize, Deserialize)]
+        struct Strict(#[serde(with = "fqdn::serde::strict")] FQDN);
+
+        #[derive(Serialize, Deserialize)]
+        struct Relaxed(#[serde(with = "fqdn::serde::relaxed")] FQDN);
+
+        assert!(serde_json::from_str::<Strict>(r#""github.com.""#).is_ok());
+        assert!(serde_json::from_str::<Strict>(r#""-github.com.""#).is_err());
+        assert!(serde_json::from_str::<Strict>(r#""aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa.com.""#).is_err());
+
+        for json in [r#""GitHub.com""#, r#""github.com.""#, r#"["github", "com"]"#] {
+            assert_eq!(serde_json::from_str::<Relaxed>(json).map(|r| r.0).ok(), Some(fqdn!("github.com")), "{json}");
+        }
+        let bytes = postcard::to_stdvec(&Relaxed(fqdn!("github.com"))).unwrap();
+        assert_eq!(postcard::from_bytes::<Relaxed>(&bytes).unwrap().0, fqdn!("github.com"));
+    }
+}
@@ -0,0 +1,470 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::fmt::Write;
+
+use crate::{Error, Fqdn, FQDN};
+
+/// Error when a name or a directive of a zone file goes wrong
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZoneError {
+
+    /// The name is not a valid FQDN, once unescaped and completed by the origin.
+    InvalidFqdn(Error),
+
+    /// A name is badly escaped (e.g. `\256`) or a directive is malformed
+    /// (e.g. a `$GENERATE` range as `3-1` or a blank owner without previous record).
+    InvalidSyntax,
+}
+
+impl From<Error> for ZoneError {
+    #[inline]
+    fn from(e: Error) -> Self { ZoneError::InvalidFqdn(e) }
+}
+
+impl fmt::Display for ZoneError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneError::InvalidFqdn(e) => fmt::Display::fmt(e, f),
+            ZoneError::InvalidSyntax => f.write_str("invalid zone file syntax"),
+        }
+    }
+}
+
+impl core::error::Error for ZoneError {
+
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ZoneError::InvalidFqdn(e) => Some(e),
+            ZoneError::InvalidSyntax => None,
+        }
+    }
+}
+
+/// The context of the names of a zone file (as specified by RFC 1035, section 5.1).
+///
+/// It tracks the current origin (`$ORIGIN`) and the owner of the previous record,
+/// so that the names found in a zone file could be converted to FQDN:
+/// * `@` stands for the current origin,
+/// * a relative name (i.e. without trailing dot) is completed by the current origin,
+/// * a blank owner stands for the owner of the previous record,
+/// * a char could be escaped as `\X` or as its decimal value `\DDD`,
+/// * `$GENERATE` templates are expanded by [`ZoneContext::generate`].
+///
+/// Conversely, [`ZoneContext::relative`] prints a FQDN as compactly as possible.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let mut zone = ZoneContext::new(fqdn!("example.com"));
+/// assert_eq![ zone.owner("@ SOA ns admin 1 3600 600 86400 300"), Ok(fqdn!("example.com")) ];
+/// assert_eq![ zone.owner("   NS ns"), Ok(fqdn!("example.com")) ];
+///
+/// zone.set_origin("sub").unwrap();
+/// assert_eq![ zone.owner("www A 192.0.2.1"), Ok(fqdn!("www.sub.example.com")) ];
+/// assert_eq![ zone.resolve("mail.example.org."), Ok(fqdn!("mail.example.org")) ];
+/// assert_eq![ zone.relative(&fqdn!("ftp.sub.example.com")), "ftp" ];
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneContext {
+    origin: FQDN,
+    previous: Option<FQDN>,
+}
+
+impl ZoneContext {
+
+    /// Creates a context with an initial origin (usually, the name of the zone).
+    #[inline]
+    pub fn new(origin: FQDN) -> Self { Self { origin, previous: None } }
+
+    /// Gets the current origin.
+    #[inline]
+    pub fn origin(&self) -> &Fqdn { &self.origin }
+
+    /// Changes the current origin, as the `$ORIGIN` directive does.
+    ///
+    /// As any other name, a relative origin is completed by the current one.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let mut zone = ZoneContext::new(fqdn!("com"));
+    /// zone.set_origin("example").unwrap();
+    /// assert_eq![ zone.origin(), fqdn!("example.com").as_ref() ];
+    /// zone.set_origin("example.org.").unwrap();
+    /// assert_eq![ zone.origin(), fqdn!("example.org").as_ref() ];
+    /// ```
+    pub fn set_origin(&mut self, origin: &str) -> Result<(), ZoneError>
+    {
+        self.origin = self.resolve(origin)?;
+        Ok(())
+    }
+
+    /// Converts a name of the zone file to a FQDN.
+    ///
+    /// The name could be `@`, absolute (with a trailing dot) or relative to the current origin,
+    /// and could contain escaped chars (`\X` or `\DDD`). Since the labels of a FQDN are still
+    /// checked, the escaped chars should be allowed by the activated features.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let zone = ZoneContext::new(fqdn!("example.com"));
+    /// assert_eq![ zone.resolve("@"), Ok(fqdn!("example.com")) ];
+    /// assert_eq![ zone.resolve("WWW"), Ok(fqdn!("www.example.com")) ];
+    /// assert_eq![ zone.resolve("\\119ww"), Ok(fqdn!("www.example.com")) ];
+    /// assert_eq![ zone.resolve("a\\.b"), Err(ZoneError::InvalidFqdn(Error::InvalidLabelChar)) ];
+    /// assert_eq![ zone.resolve("a..b"), Err(ZoneError::InvalidFqdn(Error::EmptyLabel)) ];
+    /// ```
+    pub fn resolve(&self, name: &str) -> Result<FQDN, ZoneError>
+    {
+        match name {
+            "@" => Ok(self.origin.clone()),
+            "." => Ok(FQDN::default()),
+            "" => Err(Error::EmptyLabel.into()),
+            _ => {
+                let (mut bytes, absolute) = unescape(name)?;
+                if !absolute {
+                    bytes.extend_from_slice(self.origin.as_bytes());
+                }
+                Ok(FQDN::from_vec(bytes)?)
+            }
+        }
+    }
+
+    /// Gets the owner of a record line of the zone file and remembers it for the next records.
+    ///
+    /// A line starting with a blank (space or tab) has the same owner as the previous record:
+    /// then, [`ZoneError::InvalidSyntax`] is returned if there is no previous record.
+    pub fn owner(&mut self, line: &str) -> Result<FQDN, ZoneError>
+    {
+        let owner = if line.starts_with([' ', '\t']) {
+            self.previous.clone().ok_or(ZoneError::InvalidSyntax)?
+        } else {
+            self.resolve(first_token(line))?
+        };
+        self.previous = Some(owner.clone());
+        Ok(owner)
+    }
+
+    /// Prints a FQDN relatively to the current origin, in order to emit compact zone files.
+    ///
+    /// The origin itself is printed as `@`, its subdomains as relative names and
+    /// all the other FQDN as absolute names (with their trailing dot).
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let zone = ZoneContext::new(fqdn!("example.com"));
+    /// assert_eq![ zone.relative(&fqdn!("example.com")), "@" ];
+    /// assert_eq![ zone.relative(&fqdn!("www.example.com")), "www" ];
+    /// assert_eq![ zone.relative(&fqdn!("example.org")), "example.org." ];
+    /// assert_eq![ zone.relative(&FQDN::default()), "." ];
+    /// ```
+    pub fn relative(&self, fqdn: &Fqdn) -> String
+    {
+        if fqdn == self.origin.as_ref() {
+            String::from("@")
+        } else if fqdn.is_root() {
+            String::from(".")
+        } else if fqdn.is_subdomain_of(&self.origin) {
+            let labels = fqdn.depth() - self.origin.depth();
+            fqdn.labels().take(labels).collect::<Vec<_>>().join(".")
+        } else {
+            let mut name = fqdn.labels().collect::<Vec<_>>().join(".");
+            name.push('.');
+            name
+        }
+    }
+
+    /// Expands the owner (or the target) of a `$GENERATE` directive, as BIND does.
+    ///
+    /// The range is `start-stop` or `start-stop/step`. In the template, each `$` is replaced
+    /// by the current value, which could be modified with `${offset,width,base}`
+    /// where the base is `d` (decimal), `o` (octal), `x` or `X` (hexadecimal) or
+    /// `n` or `N` (reversed nibbles separated by dots, as in `ip6.arpa`). An escaped `\$` is kept as is.
+    ///
+    /// An invalid range or modifier (including a width greater than 255) is reported with
+    /// [`ZoneError::InvalidSyntax`], while each generated name could be invalid on its own.
+    ///
+    /// # Example
+    /// ```
+    /// # use fqdn::*;
+    /// let zone = ZoneContext::new(fqdn!("2.0.192.in-addr.arpa"));
+    /// let names = zone.generate("1-3", "$").unwrap().collect::<Result<Vec<_>, _>>();
+    /// assert_eq![ names, Ok(vec![fqdn!("1.2.0.192.in-addr.arpa"), fqdn!("2.2.0.192.in-addr.arpa"), fqdn!("3.2.0.192.in-addr.arpa")]) ];
+    ///
+    /// let zone = ZoneContext::new(fqdn!("example.com"));
+    /// let names = zone.generate("8-24/8", "host-${10,3,x}").unwrap().collect::<Result<Vec<_>, _>>();
+    /// assert_eq![ names, Ok(vec![fqdn!("host-012.example.com"), fqdn!("host-01a.example.com"), fqdn!("host-022.example.com")]) ];
+    /// ```
+    pub fn generate(&self, range: &str, template: &str) -> Result<Generate<'_>, ZoneError>
+    {
+        let (bounds, step) = range.split_once('/').unwrap_or((range, "1"));
+        let (start, stop) = bounds.split_once('-').ok_or(ZoneError::InvalidSyntax)?;
+        let parse = |n: &str| n.parse::<u32>().map_err(|_| ZoneError::InvalidSyntax);
+        let (start, stop, step) = (parse(start)?, parse(stop)?, parse(step)?);
+        if start > stop || step == 0 {
+            return Err(ZoneError::InvalidSyntax);
+        }
+        Ok(Generate { zone: self, template: parse_template(template)?, next: Some(start), stop, step })
+    }
+}
+
+/// The iterator over the names generated by a `$GENERATE` directive.
+///
+/// This is returned by [`ZoneContext::generate`].
+#[derive(Debug, Clone)]
+pub struct Generate<'a> {
+    zone: &'a ZoneContext,
+    template: Vec<Segment>,
+    next: Option<u32>,
+    stop: u32,
+    step: u32,
+}
+
+impl Iterator for Generate<'_> {
+    type Item = Result<FQDN, ZoneError>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        let value = self.next?;
+        self.next = value.checked_add(self.step).filter(|&next| next <= self.stop);
+
+        let mut name = String::new();
+        for segment in &self.template {
+            match segment {
+                Segment::Text(text) => name.push_str(text),
+                Segment::Value { offset, width, base } => {
+                    let value = value as i64 + offset;
+                    if value < 0 {
+                        return Some(Err(ZoneError::InvalidSyntax));
+                    }
+                    // writing to a string never fails
+                    let _ = match base {
+                        b'o' => write!(name, "{value:0width$o}"),
+                        b'x' => write!(name, "{value:0width$x}"),
+                        b'X' => write!(name, "{value:0width$X}"),
+                        b'n' | b'N' => {
+                            // the width includes the dots between nibbles
+                            let hex = format_nibbles(value, width.div_ceil(2));
+                            name.push_str(&hex);
+                            Ok(())
+                        }
+                        _ => write!(name, "{value:0width$}"),
+                    };
+                }
+            }
+        }
+        Some(self.zone.resolve(&name))
+    }
+}
+
+// A part of a `$GENERATE` template
+#[derive(Debug, Clone)]
+enum Segment {
+    Text(String),
+    Value { offset: i64, width: usize, base: u8 },
+}
+
+fn parse_template(template: &str) -> Result<Vec<Segment>, ZoneError>
+{
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                text.push(c);
+                text.extend(chars.next());
+            }
+            '$' => {
+                if !text.is_empty() {
+                    segments.push(Segment::Text(core::mem::take(&mut text)));
+                }
+                let rest = chars.as_str();
+                match rest.strip_prefix('{') {
+                    None => segments.push(Segment::Value { offset: 0, width: 0, base: b'd' }),
+                    Some(modifier) => {
+                        let (modifier, rest) = modifier.split_once('}').ok_or(ZoneError::InvalidSyntax)?;
+                        segments.push(parse_modifier(modifier)?);
+                        chars = rest.chars();
+                    }
+                }
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    Ok(segments)
+}
+
+// Parses `offset[,width[,base]]`
+fn parse_modifier(modifier: &str) -> Result<Segment, ZoneError>
+{
+    let mut fields = modifier.split(',');
+    let offset = fields.next().unwrap_or_default().parse().map_err(|_| ZoneError::InvalidSyntax)?;
+    // the width is limited as any label, so that a huge width could not allocate a huge name
+    let width = fields.next().map_or(Ok(0), str::parse::<u8>).map_err(|_| ZoneError::InvalidSyntax)?.into();
+    let base = match fields.next() {
+        None => b'd',
+        Some(base @ ("d" | "o" | "x" | "X" | "n" | "N")) => base.as_bytes()[0],
+        Some(_) => return Err(ZoneError::InvalidSyntax),
+    };
+    match fields.next() {
+        None => Ok(Segment::Value { offset, width, base }),
+        Some(_) => Err(ZoneError::InvalidSyntax),
+    }
+}
+
+// Formats a value as reversed hexadecimal digits separated by dots (e.g. `0x1ab` as `b.a.1`)
+fn format_nibbles(mut value: i64, min: usize) -> String
+{
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut nibbles = Vec::new();
+    while value > 0 || nibbles.len() < min.max(1) {
+        nibbles.push(HEX[(value & 0xf) as usize] as char);
+        value >>= 4;
+    }
+    let mut s = String::with_capacity(2 * nibbles.len());
+    for (i, nibble) in nibbles.into_iter().enumerate() {
+        if i > 0 {
+            s.push('.');
+        }
+        s.push(nibble);
+    }
+    s
+}
+
+// Gets the first token of a line (which could contain escaped blanks)
+fn first_token(line: &str) -> &str
+{
+    let bytes = line.as_bytes();
+    let mut end = 0;
+    while end < bytes.len() && !bytes[end].is_ascii_whitespace() {
+        end += if bytes[end] == b'\\' { 2 } else { 1 };
+    }
+    &line[..end.min(bytes.len())]
+}
+
+// Converts an escaped presentation name to the byte sequence of its labels (without trailing nul)
+// and tells if the name is absolute
+fn unescape(name: &str) -> Result<(Vec<u8>, bool), ZoneError>
+{
+    let mut bytes = Vec::with_capacity(name.len() + 1);
+    let mut label = Vec::new();
+    let mut input = name.as_bytes().iter().copied();
+    let mut absolute = false;
+
+    let push_label = |label: &mut Vec<u8>, bytes: &mut Vec<u8>| match label.len() {
+        0 => Err(Error::EmptyLabel),
+        l if l > u8::MAX as usize => Err(Error::TooLongLabel),
+        l => {
+            bytes.push(l as u8);
+            bytes.append(label);
+            Ok(())
+        }
+    };
+
+    while let Some(c) = input.next() {
+        absolute = false;
+        match c {
+            b'.' => {
+                push_label(&mut label, &mut bytes)?;
+                absolute = true;
+            }
+            b'\\' => match input.next() {
+                Some(d) if d.is_ascii_digit() => {
+                    let (d2, d3) = (input.next(), input.next());
+                    let value = match (d2, d3) {
+                        (Some(d2), Some(d3)) if d2.is_ascii_digit() && d3.is_ascii_digit() => {
+                            (d - b'0') as u32 * 100 + (d2 - b'0') as u32 * 10 + (d3 - b'0') as u32
+                        }
+                        _ => return Err(ZoneError::InvalidSyntax),
+                    };
+                    label.push(u8::try_from(value).map_err(|_| ZoneError::InvalidSyntax)?);
+                }
+                Some(c) => label.push(c),
+                None => return Err(ZoneError::InvalidSyntax),
+            },
+            c => label.push(c),
+        }
+    }
+    if !absolute {
+        push_label(&mut label, &mut bytes)?;
+    }
+    Ok((bytes, absolute))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    #[test]
+    fn names()
+    {
+        let zone = ZoneContext::new(fqdn!("example.com"));
+        assert_eq!(zone.resolve("."), Ok(FQDN::default()));
+        assert_eq!(zone.resolve("Mail"), Ok(fqdn!("mail.example.com")));
+        assert_eq!(zone.resolve("mail.example.org."), Ok(fqdn!("mail.example.org")));
+        assert_eq!(zone.resolve("\\077ail"), Ok(fqdn!("mail.example.com")));
+        assert_eq!(zone.resolve("m\\a\\il"), Ok(fqdn!("mail.example.com")));
+        assert_eq!(zone.resolve("\\2560"), Err(ZoneError::InvalidSyntax));
+        assert_eq!(zone.resolve("\\07"), Err(ZoneError::InvalidSyntax));
+        assert_eq!(zone.resolve("mail\\"), Err(ZoneError::InvalidSyntax));
+        assert_eq!(zone.resolve(".mail"), Err(ZoneError::InvalidFqdn(Error::EmptyLabel)));
+        assert_eq!(zone.resolve(""), Err(ZoneError::InvalidFqdn(Error::EmptyLabel)));
+        assert_eq!(zone.resolve("m\\032il"), Err(ZoneError::InvalidFqdn(Error::InvalidLabelChar)));
+        #[cfg(not(feature = "domain-name-without-special-chars"))]
+        assert_eq!(zone.resolve("\\095sip"), Ok(fqdn!("_sip.example.com")));
+    }
+
+    #[test]
+    fn owners()
+    {
+        let mut zone = ZoneContext::new(fqdn!("example.com"));
+        assert_eq!(zone.owner("\tA 192.0.2.1"), Err(ZoneError::InvalidSyntax));
+        assert_eq!(zone.owner("www 300 IN A 192.0.2.1"), Ok(fqdn!("www.example.com")));
+        assert_eq!(zone.owner(" 300 IN AAAA 2001:db8::1"), Ok(fqdn!("www.example.com")));
+        assert_eq!(zone.owner("w\\ w A 192.0.2.1"), Err(ZoneError::InvalidFqdn(Error::InvalidLabelChar)));
+        assert_eq!(zone.owner("@ MX 10 mail"), Ok(fqdn!("example.com")));
+
+        zone.set_origin("sub").unwrap();
+        assert_eq!(zone.owner("@ NS ns"), Ok(fqdn!("sub.example.com")));
+        assert_eq!(zone.relative(&fqdn!("a.b.sub.example.com")), "a.b");
+        assert_eq!(zone.relative(&fqdn!("example.com")), "example.com.");
+        assert_eq!(zone.set_origin("a..b"), Err(ZoneError::InvalidFqdn(Error::EmptyLabel)));
+        assert_eq!(zone.origin(), fqdn!("sub.example.com").as_ref());
+    }
+
+    #[test]
+    fn generate()
+    {
+        let zone = ZoneContext::new(fqdn!("example.com"));
+        let generate = |range, template| zone.generate(range, template).map(|names| names.collect::<Vec<_>>());
+
+        assert_eq!(generate("1-2", "host$"), Ok(vec![Ok(fqdn!("host1.example.com")), Ok(fqdn!("host2.example.com"))]));
+        assert_eq!(generate("0-10/5", "${-1,2,d}.x"), Ok(vec![
+            Err(ZoneError::InvalidSyntax), Ok(fqdn!("04.x.example.com")), Ok(fqdn!("09.x.example.com"))
+        ]));
+        assert_eq!(generate("10-10", "${0,0,o}-${0,0,X}"), Ok(vec![Ok(fqdn!("12-a.example.com"))]));
+        assert_eq!(generate("4294967295-4294967295", "a$").map(|names| names.len()), Ok(1));
+
+        // nibbles
+        let zone = ZoneContext::new(fqdn!("8.b.d.0.1.0.0.2.ip6.arpa"));
+        assert_eq!(zone.generate("171-171", "${0,7,n}").unwrap().next(), Some(Ok(fqdn!("b.a.0.0.8.b.d.0.1.0.0.2.ip6.arpa"))));
+        assert_eq!(zone.generate("1-1", "$").unwrap().next(), Some(Ok(fqdn!("1.8.b.d.0.1.0.0.2.ip6.arpa"))));
+
+        // invalid directives
+        for (range, template) in [("2-1", "$"), ("1", "$"), ("1-2/0", "$"), ("a-2", "$"), ("1-2", "${1"), ("1-2", "${1,2,z}"), ("1-2", "${1,2,d,4}"),
+                                 ("1-10", "h${0,99999999999}"), ("1-10", "${0,256,n}")] {
+            assert_eq!(zone.generate(range, template).err(), Some(ZoneError::InvalidSyntax), "{range} {template}");
+        }
+        assert_eq!(zone.generate("1-1", "\\$").unwrap().next(), Some(Err(ZoneError::InvalidFqdn(Error::InvalidLabelChar))));
+    }
+}
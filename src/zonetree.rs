@@ -0,0 +1,316 @@
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+use crate::{Fqdn, FQDN};
+
+/// Error when a name could not be inserted in a [`ZoneTree`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ZoneTreeError {
+
+    /// The name is not a subdomain of the apex of the zone.
+    OutOfZone,
+
+    /// The apex of the zone could not be a zone cut.
+    ApexDelegation,
+}
+
+impl fmt::Display for ZoneTreeError {
+
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneTreeError::OutOfZone => f.write_str("name out of the zone"),
+            ZoneTreeError::ApexDelegation => f.write_str("delegation of the zone apex"),
+        }
+    }
+}
+
+impl core::error::Error for ZoneTreeError { }
+
+/// The answer of a [`ZoneTree`] to a query name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneLookup<'a, V> {
+    /// The query name is an owner of the zone.
+    Exact(&'a V),
+    /// The query name exists but has no value: it is an empty non-terminal
+    /// (i.e. only its subdomains are owners).
+    NoData,
+    /// The query name does not exist but is synthesized from the wildcard `*.<closest encloser>`.
+    Wildcard {
+        /// The value of the wildcard owner
+        value: &'a V,
+        /// The longest existing ancestor of the query name
+        closest_encloser: &'a Fqdn,
+        /// The ancestor of the query name one label longer than the closest encloser
+        next_closer: &'a Fqdn,
+    },
+    /// The query name is at or below a zone cut, so the answer is a referral.
+    Delegation {
+        /// The delegated domain
+        cut: &'a Fqdn,
+        /// The value of the delegation point (usually, its NS records)
+        value: &'a V,
+    },
+    /// The query name does not exist and there is no wildcard to synthesize it.
+    NxDomain {
+        /// The longest existing ancestor of the query name
+        closest_encloser: &'a Fqdn,
+        /// The ancestor of the query name one label longer than the closest encloser
+        next_closer: &'a Fqdn,
+    },
+    /// The query name is not in the zone.
+    OutOfZone,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node<V> {
+    value: Option<V>,
+    // the value of the owner `*.<this node>`
+    wildcard: Option<V>,
+    cut: bool,
+}
+
+impl<V> Default for Node<V> {
+    #[inline]
+    fn default() -> Self { Self { value: None, wildcard: None, cut: false } }
+}
+
+/// The owners of a DNS zone, looked up as an authoritative server does (RFC 1034, section 4.3.2).
+///
+/// A query name is answered with its value if it is an owner, or else by the wildcard
+/// of its closest encloser, as specified by the RFC 4592:
+/// * a wildcard only matches the names which do not exist, so an empty non-terminal
+///   (a domain whose only subdomains are owners) blocks it,
+/// * a wildcard matches any number of labels below its parent,
+/// * no name is synthesized below a zone cut, whose delegation is returned instead.
+///
+/// Since `*` is not a valid label, the wildcard owner `*.<domain>` is inserted with
+/// [`ZoneTree::insert_wildcard`] and a name containing a literal `*` could not be queried.
+///
+/// # Example
+/// ```
+/// # use fqdn::*;
+/// let mut zone = ZoneTree::new(fqdn!("example"));
+/// zone.insert(fqdn!("host1.example"), "192.0.2.1")?;
+/// zone.insert_wildcard(fqdn!("example"), "192.0.2.100")?;
+/// zone.insert_delegation(fqdn!("subdel.example"), "ns.example.com")?;
+///
+/// assert_eq![ zone.lookup(&fqdn!("host1.example")), ZoneLookup::Exact(&"192.0.2.1") ];
+/// let qname = fqdn!("host3.example");
+/// assert![ matches!(zone.lookup(&qname), ZoneLookup::Wildcard { value: &"192.0.2.100", .. }) ];
+/// let qname = fqdn!("host.subdel.example");
+/// assert![ matches!(zone.lookup(&qname), ZoneLookup::Delegation { value: &"ns.example.com", .. }) ];
+/// assert_eq![ zone.lookup(&fqdn!("example.com")), ZoneLookup::OutOfZone ];
+/// # Ok::<(), ZoneTreeError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneTree<V> {
+    apex: FQDN,
+    // all the existing names (owners and empty non-terminals)
+    nodes: BTreeMap<FQDN, Node<V>>,
+}
+
+impl<V> ZoneTree<V> {
+
+    /// Creates an empty zone (only its apex exists).
+    pub fn new(apex: FQDN) -> Self
+    {
+        let mut nodes = BTreeMap::new();
+        nodes.insert(apex.clone(), Node::default());
+        Self { apex, nodes }
+    }
+
+    /// Gets the apex of the zone.
+    #[inline]
+    pub fn apex(&self) -> &Fqdn { &self.apex }
+
+    /// Counts the existing names of the zone, including its apex and its empty non-terminals.
+    #[inline]
+    pub fn len(&self) -> usize { self.nodes.len() }
+
+    /// Checks if no value is stored in the zone.
+    pub fn is_empty(&self) -> bool
+    {
+        self.nodes.values().all(|node| node.value.is_none() && node.wildcard.is_none())
+    }
+
+    // gets the node of a name of the zone, creating it and its missing ancestors
+    fn node_mut(&mut self, fqdn: FQDN) -> Result<&mut Node<V>, ZoneTreeError>
+    {
+        if !fqdn.is_subdomain_of(&self.apex) {
+            return Err(ZoneTreeError::OutOfZone);
+        }
+        for name in fqdn.ancestors_within(&self.apex) {
+            if !self.nodes.contains_key(name) {
+                self.nodes.insert(name.into(), Node::default());
+            }
+        }
+        Ok(self.nodes.get_mut(&fqdn).expect("inserted node"))
+    }
+
+    /// Sets the value of an owner and returns the previous one.
+    ///
+    /// Its ancestors which are not owners become empty non-terminals.
+    /// [`ZoneTreeError::OutOfZone`] is returned if the owner is not in the zone.
+    pub fn insert(&mut self, owner: FQDN, value: V) -> Result<Option<V>, ZoneTreeError>
+    {
+        Ok(self.node_mut(owner)?.value.replace(value))
+    }
+
+    /// Sets the value of the wildcard owner `*.<parent>` and returns the previous one.
+    pub fn insert_wildcard(&mut self, parent: FQDN, value: V) -> Result<Option<V>, ZoneTreeError>
+    {
+        Ok(self.node_mut(parent)?.wildcard.replace(value))
+    }
+
+    /// Sets a zone cut (i.e. a delegation to another zone) and returns the previous value of its owner.
+    ///
+    /// The apex could not be a zone cut: then [`ZoneTreeError::ApexDelegation`] is returned.
+    pub fn insert_delegation(&mut self, cut: FQDN, value: V) -> Result<Option<V>, ZoneTreeError>
+    {
+        if cut == self.apex {
+            return Err(ZoneTreeError::ApexDelegation);
+        }
+        let node = self.node_mut(cut)?;
+        node.cut = true;
+        Ok(node.value.replace(value))
+    }
+
+    /// Gets the value of an owner, without wildcard synthesis nor delegation.
+    #[inline]
+    pub fn get(&self, owner: &Fqdn) -> Option<&V>
+    {
+        self.nodes.get(owner).and_then(|node| node.value.as_ref())
+    }
+
+    /// Gets the value of the wildcard owner `*.<parent>`.
+    #[inline]
+    pub fn get_wildcard(&self, parent: &Fqdn) -> Option<&V>
+    {
+        self.nodes.get(parent).and_then(|node| node.wildcard.as_ref())
+    }
+
+    /// Answers a query name.
+    ///
+    /// The existing names are walked down from the apex: the first zone cut returns a delegation,
+    /// and the first name which does not exist is the next closer name, whose parent is the
+    /// closest encloser (RFC 4592, section 3.3.1). Then, the query name is synthesized
+    /// from the wildcard of the closest encloser if any.
+    pub fn lookup<'a>(&'a self, qname: &'a Fqdn) -> ZoneLookup<'a, V>
+    {
+        // the walk starts at the apex (which is excluded from the hierarchy if it is the top domain)
+        let mut closest_encloser = self.apex.as_ref();
+        for name in qname.ancestors_within(&self.apex) {
+            let Some(node) = self.nodes.get(name) else {
+                // the existing ancestors are walked first, so the parent exists
+                return match self.nodes.get(closest_encloser).and_then(|node| node.wildcard.as_ref()) {
+                    Some(value) => ZoneLookup::Wildcard { value, closest_encloser, next_closer: name },
+                    None => ZoneLookup::NxDomain { closest_encloser, next_closer: name },
+                };
+            };
+            if node.cut {
+                // a zone cut always has a value
+                if let Some(value) = &node.value {
+                    return ZoneLookup::Delegation { cut: name, value };
+                }
+            }
+            closest_encloser = name;
+        }
+        match self.nodes.get(qname) {
+            None => ZoneLookup::OutOfZone,
+            Some(Node { value: Some(value), .. }) => ZoneLookup::Exact(value),
+            Some(_) => ZoneLookup::NoData,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate as fqdn;
+    use fqdn::*;
+
+    // the zone of the RFC 4592, section 2.2.1 (without `sub.*.example.` which has a literal `*`)
+    fn rfc4592() -> ZoneTree<&'static str>
+    {
+        let mut zone = ZoneTree::new(fqdn!("example"));
+        zone.insert(fqdn!("example"), "SOA NS").unwrap();
+        zone.insert_wildcard(fqdn!("example"), "TXT MX").unwrap();
+        zone.insert(fqdn!("host1.example"), "A").unwrap();
+        #[cfg(not(feature = "domain-name-without-special-chars"))]
+        {
+            zone.insert(fqdn!("_ssh._tcp.host1.example"), "SRV").unwrap();
+            zone.insert(fqdn!("_ssh._tcp.host2.example"), "SRV").unwrap();
+        }
+        zone.insert_delegation(fqdn!("subdel.example"), "NS").unwrap();
+        zone
+    }
+
+    #[test]
+    fn rfc4592_examples()
+    {
+        let zone = rfc4592();
+        let example = fqdn!("example");
+
+        // section 2.2.1: synthesized answers
+        let qname = fqdn!("host3.example");
+        assert_eq!(zone.lookup(&qname), ZoneLookup::Wildcard { value: &"TXT MX", closest_encloser: &example, next_closer: &qname });
+        let qname = fqdn!("foo.bar.example");
+        assert_eq!(zone.lookup(&qname), ZoneLookup::Wildcard { value: &"TXT MX", closest_encloser: &example, next_closer: &fqdn!("bar.example") });
+
+        // section 2.2.1: not synthesized
+        assert_eq!(zone.lookup(&fqdn!("host1.example")), ZoneLookup::Exact(&"A"));
+        let qname = fqdn!("host.subdel.example");
+        assert_eq!(zone.lookup(&qname), ZoneLookup::Delegation { cut: &fqdn!("subdel.example"), value: &"NS" });
+        assert_eq!(zone.lookup(&fqdn!("subdel.example")), ZoneLookup::Delegation { cut: &fqdn!("subdel.example"), value: &"NS" });
+
+        #[cfg(not(feature = "domain-name-without-special-chars"))]
+        {
+            // `_tcp.host1.example` is an empty non-terminal which blocks the wildcard
+            let qname = fqdn!("_telnet._tcp.host1.example");
+            assert_eq!(zone.lookup(&qname), ZoneLookup::NxDomain { closest_encloser: &fqdn!("_tcp.host1.example"), next_closer: &qname });
+            assert_eq!(zone.lookup(&fqdn!("_tcp.host1.example")), ZoneLookup::NoData);
+
+            // section 3.3.1: `host2.example` is an empty non-terminal too
+            assert_eq!(zone.lookup(&fqdn!("host2.example")), ZoneLookup::NoData);
+            let qname = fqdn!("x.host2.example");
+            assert_eq!(zone.lookup(&qname), ZoneLookup::NxDomain { closest_encloser: &fqdn!("host2.example"), next_closer: &qname });
+        }
+    }
+
+    #[test]
+    fn tree()
+    {
+        let mut zone = rfc4592();
+        assert_eq!(zone.apex(), fqdn!("example").as_ref());
+        assert_eq!(zone.lookup(&fqdn!("com")), ZoneLookup::OutOfZone);
+        assert_eq!(zone.lookup(&FQDN::default()), ZoneLookup::OutOfZone);
+        assert_eq!(zone.insert(fqdn!("example.com"), "A"), Err(ZoneTreeError::OutOfZone));
+        assert_eq!(zone.insert_wildcard(FQDN::default(), "A"), Err(ZoneTreeError::OutOfZone));
+        assert_eq!(zone.insert_delegation(fqdn!("example"), "NS"), Err(ZoneTreeError::ApexDelegation));
+        assert_eq!(zone.insert_delegation(fqdn!("example.com"), "NS"), Err(ZoneTreeError::OutOfZone));
+
+        // replacing values
+        assert_eq!(zone.insert(fqdn!("host1.example"), "A AAAA"), Ok(Some("A")));
+        assert_eq!(zone.get(&fqdn!("host1.example")), Some(&"A AAAA"));
+        assert_eq!(zone.get(&fqdn!("host3.example")), None);
+        assert_eq!(zone.get_wildcard(&fqdn!("example")), Some(&"TXT MX"));
+
+        // a deeper wildcard takes precedence below its parent, and its parent exists
+        zone.insert_wildcard(fqdn!("deep.sub.example"), "CNAME").unwrap();
+        assert_eq!(zone.lookup(&fqdn!("sub.example")), ZoneLookup::NoData);
+        assert!(matches!(zone.lookup(&fqdn!("a.b.deep.sub.example")), ZoneLookup::Wildcard { value: &"CNAME", .. }));
+        assert!(matches!(zone.lookup(&fqdn!("other.sub.example")), ZoneLookup::NxDomain { .. }));
+
+        // glue below a zone cut is hidden by the delegation
+        zone.insert(fqdn!("ns.subdel.example"), "A").unwrap();
+        assert!(matches!(zone.lookup(&fqdn!("ns.subdel.example")), ZoneLookup::Delegation { .. }));
+
+        // empty zone
+        let zone = ZoneTree::<()>::new(FQDN::default());
+        assert!(zone.is_empty());
+        assert_eq!(zone.len(), 1);
+        assert_eq!(zone.lookup(&FQDN::default()), ZoneLookup::NoData);
+        let qname = fqdn!("github.com");
+        assert_eq!(zone.lookup(&qname), ZoneLookup::NxDomain { closest_encloser: &FQDN::default(), next_closer: &fqdn!("com") });
+    }
+}